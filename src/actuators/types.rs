@@ -1,10 +1,119 @@
 use crate::control::types::ControlBus;
+use crate::estimation::types::EstimationBus;
+use crate::fsw_math::inv;
+use crate::fsw_types::ParamBus;
+use crate::scheduler::{Phase, RateGroup, Scheduler};
+use crate::time::TimeBus;
+use altai_rs::meta::types::Generic1D;
 
 #[derive(Clone, Debug, Default)]
-pub struct ActuatorBus {}
+pub struct ActuatorBus {
+    // RWA
+    pub rw_torque_cmd: Generic1D, // Per-wheel torque command about spin axis [Nm]
+    pub rw_momentum_cmd: Generic1D, // Per-wheel momentum expected after commands [Nms]
+    pub rw_scale: f64,            // Saturation scaling applied (1 = unsaturated)
+    pub rw_valid: bool,
+
+    // MTQ
+    pub mtq_dipole_cmd: Generic1D, // Per-torquer dipole command [Am^2]
+
+    // SADA
+    pub sada_angle_cmd: Generic1D, // Per-SADA angle command [rad]
+    pub sada_rate_cmd: Generic1D,  // Per-SADA rate command [rad/s]
+}
 
 impl ActuatorBus {
-    pub fn process(_curr_ctrl: &ControlBus) -> Self {
-        Self {}
+    pub fn process(
+        &mut self,
+        curr_est: &EstimationBus,
+        curr_ctrl: &ControlBus,
+        time_bus: &TimeBus,
+        prev_act: &ActuatorBus,
+        param_bus: &ParamBus,
+    ) {
+        let arch = &param_bus.acs_actuators;
+        let dt = time_bus.dt();
+
+        // MTQ; saturate preserving dipole direction
+        let dipole_peak = curr_ctrl
+            .dipole_cmd_sc
+            .fold(0., |acc: f64, m| acc.max(m.abs()));
+        self.mtq_dipole_cmd = if dipole_peak > arch.mtq_dipole_max {
+            &curr_ctrl.dipole_cmd_sc * (arch.mtq_dipole_max / dipole_peak)
+        } else {
+            curr_ctrl.dipole_cmd_sc.to_owned()
+        };
+
+        // SADA; enforce hard stops and rate limit
+        self.sada_angle_cmd = curr_ctrl
+            .sada_angle_cmd
+            .mapv(|angle| angle.clamp(arch.sada_angle_min, arch.sada_angle_max));
+        self.sada_rate_cmd = curr_ctrl
+            .sada_rate_cmd
+            .mapv(|rate| rate.clamp(-arch.sada_rate_max, arch.sada_rate_max));
+
+        let n_rw = arch.n_rw();
+
+        // Measured momentum; else carry commanded momentum forward (resize on config change)
+        let h_prev = if curr_est.h_rw_valid && curr_est.h_rw.len() == n_rw {
+            curr_est.h_rw.to_owned()
+        } else if prev_act.rw_momentum_cmd.len() == n_rw {
+            prev_act.rw_momentum_cmd.to_owned()
+        } else {
+            Generic1D::zeros(n_rw)
+        };
+
+        // Wheel torque reacts on SC: T_sc = -A * u_rw  =>  u_rw = -pinv(A) * T_sc
+        let a = &arch.rw_axes;
+        let Some(aat_inv) = inv(&a.dot(&a.t())) else {
+            log::error!("RW spin axes do not span 3-axis; no wheel commands");
+            self.rw_torque_cmd = Generic1D::zeros(n_rw);
+            self.rw_momentum_cmd = h_prev;
+            self.rw_scale = 0.;
+            self.rw_valid = false;
+            return;
+        };
+        let u_rw = -a.t().dot(&aat_inv).dot(&curr_ctrl.torque_cmd_sc);
+
+        // Common scale keeps the body torque direction
+        let scale = u_rw
+            .iter()
+            .zip(h_prev.iter())
+            .fold(1f64, |scale, (&u, &h)| {
+                // Torque limit
+                let mut scale = scale;
+                if u.abs() > arch.rw_torque_max {
+                    scale = scale.min(arch.rw_torque_max / u.abs());
+                }
+
+                // Momentum limit; only restrict torque that spins the wheel up
+                if u * h > 0. {
+                    let u_avail = ((arch.rw_momentum_max - h.abs()) / dt).max(0.);
+                    scale = scale.min(u_avail / u.abs());
+                }
+                scale
+            });
+
+        self.rw_torque_cmd = u_rw * scale;
+        self.rw_momentum_cmd = h_prev + &self.rw_torque_cmd * dt;
+        self.rw_scale = scale;
+        self.rw_valid = true;
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        scheduler.register(
+            "ACTUATORS",
+            RateGroup::FAST,
+            Phase::ACTUATORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.actuator_bus.process(
+                    &curr.estimation_bus,
+                    &curr.control_bus,
+                    time_bus,
+                    &prev.actuator_bus,
+                    param_bus,
+                )
+            },
+        );
     }
 }
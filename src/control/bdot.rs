@@ -0,0 +1,12 @@
+use altai_rs::{meta::types::Generic1D, veclib::mfcross};
+
+// Rate-based B-dot: m = k/|B|^2 * (w x B)
+// Resulting torque m x B = -k * w_perp, so k is a damping gain [Nms]
+pub fn bdot_dipole(omega_sc: &Generic1D, b_sc: &Generic1D, gain: f64) -> Generic1D {
+    let b2 = b_sc.dot(b_sc);
+    if b2 < 1e-18 {
+        log::warn!("Magnetic field too weak for B-dot; no dipole commanded");
+        return Generic1D::zeros(3);
+    }
+    mfcross(omega_sc, b_sc) * (gain / b2)
+}
@@ -1 +1,5 @@
 pub mod types;
+
+// Control Laws
+pub mod bdot;
+pub mod sada;
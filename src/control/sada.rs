@@ -0,0 +1,19 @@
+use altai_rs::{meta::types::Generic1D, veclib::mfcross};
+
+// Array angle about the SADA axis that points the array normal at the sun
+// projection in the rotation plane; None if the sun lies along the axis
+pub fn sada_sun_angle(
+    u_sun_sc: &Generic1D,
+    sada_axis: &Generic1D,
+    normal_zero: &Generic1D,
+) -> Option<f64> {
+    let u_proj = u_sun_sc - &(sada_axis * sada_axis.dot(u_sun_sc));
+    if u_proj.dot(&u_proj) < 1e-6 {
+        return None;
+    }
+    Some(
+        sada_axis
+            .dot(&mfcross(normal_zero, &u_proj))
+            .atan2(normal_zero.dot(&u_proj)),
+    )
+}
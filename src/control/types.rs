@@ -1,10 +1,235 @@
+use crate::control::bdot::bdot_dipole;
+use crate::control::sada::sada_sun_angle;
+use crate::fsw_math::{qerror, quat2dcm, quat2rotvec, wrap_angle};
+use crate::fsw_types::ParamBus;
+use crate::reference::types::Reference;
+use crate::scheduler::{Phase, RateGroup, Scheduler};
+use crate::sensors::types::{Sensor, SensorBus};
+use crate::time::TimeBus;
 use crate::{estimation::types::EstimationBus, reference::types::ReferenceBus};
+use altai_rs::{meta::types::Generic1D, veclib::mfcross};
 
-#[derive(Clone, Debug, Default)]
-pub struct ControlBus {}
+#[derive(Clone, Debug)]
+pub struct ControlBus {
+    pub mode: ControlMode,
+    pub torque_cmd_sc: Generic1D,  // Commanded torque in SC frame [Nm]
+    pub dipole_cmd_sc: Generic1D,  // Commanded dipole in SC frame [Am^2]
+    pub sada_angle_cmd: Generic1D, // Commanded array angle per SADA [rad]
+    pub sada_rate_cmd: Generic1D,  // Commanded array rate per SADA [rad/s]
+    pub sada_park: bool,
+    pub att_err: Generic1D, // Attitude error (rotation vector) in SC frame [rad]
+    pub rate_err: Generic1D, // Rate error in SC frame [rad/s]
+    pub int_err: Generic1D, // Integrated attitude error [rad*s]
+    pub saturated: bool,
+    pub active: bool,
+}
 
 impl ControlBus {
-    pub fn process(_curr_est: &EstimationBus, _curr_ref: &ReferenceBus) -> Self {
-        Self {}
+    pub fn process(
+        &mut self,
+        tlm_sensor: &SensorBus,
+        curr_est: &EstimationBus,
+        curr_ref: &ReferenceBus,
+        time_bus: &TimeBus,
+        prev_ctrl: &ControlBus,
+        param_bus: &ParamBus,
+    ) {
+        // Reset outputs; carry commands forward
+        *self = Self {
+            mode: prev_ctrl.mode,
+            sada_park: prev_ctrl.sada_park,
+            ..Self::default()
+        };
+
+        match self.mode {
+            ControlMode::POINTING => {
+                self.pointing(curr_est, curr_ref, time_bus.dt(), prev_ctrl, param_bus)
+            }
+            ControlMode::DETUMBLE => self.detumble(tlm_sensor, param_bus),
+        }
+
+        // Arrays track independent of attitude control mode
+        self.sada(tlm_sensor, curr_est, prev_ctrl, param_bus);
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        scheduler.register(
+            "CONTROL",
+            RateGroup::FAST,
+            Phase::CONTROL,
+            |curr, prev, time_bus, param_bus| {
+                curr.control_bus.process(
+                    &curr.tlm_sensor_bus,
+                    &curr.estimation_bus,
+                    &curr.reference_bus,
+                    time_bus,
+                    &prev.control_bus,
+                    param_bus,
+                )
+            },
+        );
+    }
+
+    pub fn command_mode(&mut self, mode: ControlMode) {
+        self.mode = mode;
+    }
+
+    pub fn command_sada_park(&mut self, park: bool) {
+        self.sada_park = park;
+    }
+
+    fn sada(
+        &mut self,
+        tlm_sensor: &SensorBus,
+        curr_est: &EstimationBus,
+        prev_ctrl: &ControlBus,
+        param_bus: &ParamBus,
+    ) {
+        let act = &param_bus.acs_actuators;
+        let n_sada = act.n_sada();
+
+        // Measured angle per usable SADA
+        let angle_meas: Vec<Option<f64>> = match tlm_sensor
+            .sada_bus()
+            .filter(|sada| sada.hardware_subtest() == 0 && sada.angle().len() == n_sada)
+        {
+            Some(sada) => (0..n_sada)
+                .map(|idx| sada.usable()[idx].then(|| sada.angle()[idx]))
+                .collect(),
+            None => vec![None; n_sada],
+        };
+        let u_sun_sc = (curr_est.att_valid && curr_est.sun_valid)
+            .then(|| quat2dcm(&curr_est.q_sc_eci).dot(&curr_est.u_sun_eci));
+        let angle_prev = if prev_ctrl.sada_angle_cmd.len() == n_sada {
+            prev_ctrl.sada_angle_cmd.to_owned()
+        } else {
+            Generic1D::zeros(n_sada)
+        };
+
+        // Park, else track sun, else hold measured / last commanded angle
+        self.sada_angle_cmd = Generic1D::from_shape_fn(n_sada, |idx| {
+            let target = if self.sada_park {
+                Some(act.sada_park_angle)
+            } else {
+                u_sun_sc.as_ref().and_then(|u_sun_sc| {
+                    sada_sun_angle(
+                        u_sun_sc,
+                        &act.sada_axes.column(idx).to_owned(),
+                        &act.sada_normals.column(idx).to_owned(),
+                    )
+                })
+            };
+            target
+                .or(angle_meas[idx])
+                .unwrap_or(angle_prev[idx])
+                .clamp(act.sada_angle_min, act.sada_angle_max)
+        });
+
+        // Rate; wrap error only for continuous rotation (no hard stops)
+        let continuous = act.sada_angle_max - act.sada_angle_min >= std::f64::consts::TAU;
+        self.sada_rate_cmd = Generic1D::from_shape_fn(n_sada, |idx| {
+            angle_meas[idx].map_or(0., |angle_meas| {
+                let err = self.sada_angle_cmd[idx] - angle_meas;
+                let err = if continuous { wrap_angle(err) } else { err };
+                (param_bus.acs_control.sada_gain * err).clamp(-act.sada_rate_max, act.sada_rate_max)
+            })
+        });
+    }
+
+    fn detumble(&mut self, tlm_sensor: &SensorBus, param_bus: &ParamBus) {
+        // Raw IMU rate; attitude estimate not required
+        let gyro_sc = tlm_sensor
+            .imu_bus()
+            .filter(|imu| imu.hardware_subtest() == 0)
+            .map(|imu| imu.gyro_fused().to_owned());
+
+        if let (Some(gyro_sc), Some(b_sc)) = (gyro_sc, tlm_sensor.b_sc()) {
+            self.dipole_cmd_sc = bdot_dipole(&gyro_sc, &b_sc, param_bus.acs_control.bdot_gain);
+            self.rate_err = gyro_sc;
+            self.active = true;
+        }
     }
+
+    fn pointing(
+        &mut self,
+        curr_est: &EstimationBus,
+        curr_ref: &ReferenceBus,
+        dt: f64,
+        prev_ctrl: &ControlBus,
+        param_bus: &ParamBus,
+    ) {
+        let arch = &param_bus.acs_control;
+
+        // No control without a valid estimate / reference
+        self.active = curr_est.att_valid && !curr_ref.ref_err && curr_ref.mode != Reference::IDLE;
+        if !self.active {
+            return;
+        }
+
+        // Errors; reference rate/accel rotated into SC frame
+        let dq = qerror(&curr_est.q_sc_eci, &curr_ref.q_ref_eci);
+        let t_sc_ref = quat2dcm(&dq);
+        let omega_ref_sc = t_sc_ref.dot(&curr_ref.omega_ref);
+        let alpha_ref_sc = t_sc_ref.dot(&curr_ref.alpha_ref);
+        self.att_err = quat2rotvec(&dq);
+        self.rate_err = &curr_est.omega_sc - &omega_ref_sc;
+
+        // Feed-forward: J*alpha_ref + w x (Jw + h_rw)
+        let j = &param_bus.acs_multibody.j_multibody;
+        let torque_ff = if j.dim() == (3, 3) {
+            let h_sc = j.dot(&curr_est.omega_sc) + &curr_est.h_rw_sc;
+            j.dot(&alpha_ref_sc) + mfcross(&curr_est.omega_sc, &h_sc)
+        } else {
+            log::error!("Invalid multibody inertia {:?}; no feed-forward", j.dim());
+            Generic1D::zeros(3)
+        };
+
+        // PID
+        let int_err = &prev_ctrl.int_err + &(&self.att_err * dt);
+        let torque =
+            torque_ff - &arch.kp * &self.att_err - &arch.kd * &self.rate_err - &arch.ki * &int_err;
+
+        // Saturate preserving direction
+        let torque_peak = torque.fold(0., |acc: f64, t| acc.max(t.abs()));
+        self.saturated = torque_peak > arch.torque_max;
+        self.torque_cmd_sc = if self.saturated {
+            torque * (arch.torque_max / torque_peak)
+        } else {
+            torque
+        };
+
+        // Anti-windup: hold integrator while saturated or far from target, then clamp
+        self.int_err =
+            if self.saturated || self.att_err.dot(&self.att_err).sqrt() > arch.int_err_max {
+                prev_ctrl.int_err.to_owned()
+            } else {
+                int_err
+            }
+            .mapv(|x| x.clamp(-arch.int_limit, arch.int_limit));
+    }
+}
+
+impl Default for ControlBus {
+    fn default() -> Self {
+        Self {
+            mode: ControlMode::POINTING,
+            torque_cmd_sc: Generic1D::zeros(3),
+            dipole_cmd_sc: Generic1D::zeros(3),
+            sada_angle_cmd: Generic1D::zeros(0),
+            sada_rate_cmd: Generic1D::zeros(0),
+            sada_park: false,
+            att_err: Generic1D::zeros(3),
+            rate_err: Generic1D::zeros(3),
+            int_err: Generic1D::zeros(3),
+            saturated: false,
+            active: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ControlMode {
+    #[default]
+    POINTING, // Quaternion-feedback PID on RWA
+    DETUMBLE, // B-dot on MTQ
 }
@@ -0,0 +1,91 @@
+use crate::estimation::types::EstimationBus;
+use crate::fsw_math::{inv, qerror, qmult, qnormalize, rotvec2quat, skew};
+use crate::fsw_types::EstimationArchitecture;
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::s;
+
+// Multiplicative Extended Kalman Filter
+// Error state: [dtheta (3), dbias (3)] w/ q_true = dq(dtheta) ⊗ q_est
+
+pub fn initialize(est: &mut EstimationBus, q_meas: &Generic1D, arch: &EstimationArchitecture) {
+    est.q_sc_eci = qnormalize(q_meas);
+    est.gyro_bias = Generic1D::zeros(3);
+
+    est.cov_att = Generic2D::zeros((6, 6));
+    est.cov_att
+        .slice_mut(s![0..3, 0..3])
+        .assign(&(Generic2D::eye(3) * arch.sigma_att_init.powi(2)));
+    est.cov_att
+        .slice_mut(s![3..6, 3..6])
+        .assign(&(Generic2D::eye(3) * arch.sigma_bias_init.powi(2)));
+
+    est.att_initialized = true;
+}
+
+pub fn propagate(
+    est: &mut EstimationBus,
+    gyro_sc: &Generic1D,
+    dt: f64,
+    arch: &EstimationArchitecture,
+) {
+    // Bias-corrected rate
+    let omega = gyro_sc - &est.gyro_bias;
+
+    // Attitude
+    est.q_sc_eci = qnormalize(&qmult(&rotvec2quat(&(&omega * dt)), &est.q_sc_eci));
+    est.omega_sc = omega.to_owned();
+
+    // State transition (2nd order)
+    let mut f = Generic2D::zeros((6, 6));
+    f.slice_mut(s![0..3, 0..3]).assign(&-skew(&omega));
+    f.slice_mut(s![0..3, 3..6]).assign(&-Generic2D::eye(3));
+    let phi = Generic2D::eye(6) + &f * dt + f.dot(&f) * (dt * dt / 2.);
+
+    // Process noise
+    let (sv2, su2) = (arch.sigma_gyro_arw.powi(2), arch.sigma_gyro_rrw.powi(2));
+    let mut q = Generic2D::zeros((6, 6));
+    q.slice_mut(s![0..3, 0..3])
+        .assign(&(Generic2D::eye(3) * (sv2 * dt + su2 * dt.powi(3) / 3.)));
+    q.slice_mut(s![0..3, 3..6])
+        .assign(&(Generic2D::eye(3) * (-su2 * dt.powi(2) / 2.)));
+    q.slice_mut(s![3..6, 0..3])
+        .assign(&(Generic2D::eye(3) * (-su2 * dt.powi(2) / 2.)));
+    q.slice_mut(s![3..6, 3..6])
+        .assign(&(Generic2D::eye(3) * (su2 * dt)));
+
+    let cov = phi.dot(&est.cov_att).dot(&phi.t()) + q;
+    est.cov_att = (&cov + &cov.t()) / 2.;
+}
+
+// Returns false if the measurement could not be fused
+// r_meas: measurement attitude error covariance in SC frame [rad^2]
+pub fn update(est: &mut EstimationBus, q_meas: &Generic1D, r_meas: &Generic2D) -> bool {
+    // Residual (small-angle)
+    let dq = qerror(q_meas, &est.q_sc_eci);
+    let z = dq.slice(s![0..3]).to_owned() * (2. / dq[3]);
+
+    // Gain; H = [I3 0]
+    let innov_cov = est.cov_att.slice(s![0..3, 0..3]).to_owned() + r_meas;
+    let Some(innov_cov_inv) = inv(&innov_cov) else {
+        log::warn!("MEKF innovation covariance singular; skipping STA update");
+        return false;
+    };
+    let k = est.cov_att.slice(s![.., 0..3]).dot(&innov_cov_inv);
+    let dx = k.dot(&z);
+
+    // Covariance (Joseph form)
+    let mut ikh = Generic2D::eye(6);
+    ikh.slice_mut(s![.., 0..3]).scaled_add(-1., &k);
+    let cov = ikh.dot(&est.cov_att).dot(&ikh.t()) + k.dot(r_meas).dot(&k.t());
+    est.cov_att = (&cov + &cov.t()) / 2.;
+
+    // Reset
+    est.q_sc_eci = qnormalize(&qmult(
+        &rotvec2quat(&dx.slice(s![0..3]).to_owned()),
+        &est.q_sc_eci,
+    ));
+    est.gyro_bias = &est.gyro_bias + &dx.slice(s![3..6]);
+    est.omega_sc = &est.omega_sc - &dx.slice(s![3..6]);
+
+    true
+}
@@ -1 +1,3 @@
+pub mod mekf;
+pub mod orbit;
 pub mod types;
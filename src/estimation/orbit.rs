@@ -0,0 +1,137 @@
+use crate::estimation::types::EstimationBus;
+use crate::fsw_math::inv;
+use crate::fsw_types::EstimationArchitecture;
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, concatenate, s, Axis};
+
+// Earth (EGM-96 / WGS-84)
+pub const MU_EARTH: f64 = 3.986004418e14; // [m^3/s^2]
+pub const R_EARTH: f64 = 6378137.0; // [m]
+pub const J2_EARTH: f64 = 1.08262668e-3;
+
+// Orbit Extended Kalman Filter
+// State: [r_eci (3), v_eci (3)] w/ two-body + J2 dynamics
+
+pub fn initialize(
+    est: &mut EstimationBus,
+    r_meas: &Generic1D,
+    v_meas: &Generic1D,
+    arch: &EstimationArchitecture,
+) {
+    est.r_eci = r_meas.to_owned();
+    est.v_eci = v_meas.to_owned();
+    est.cov_orbit = measurement_noise(arch);
+    est.t_since_fix = 0.;
+    est.orbit_initialized = true;
+}
+
+pub fn propagate(est: &mut EstimationBus, dt: f64, arch: &EstimationArchitecture) {
+    // State
+    let r0 = est.r_eci.to_owned();
+    (est.r_eci, est.v_eci) = propagate_state(&est.r_eci, &est.v_eci, dt);
+
+    // State transition (2nd order, two-body gravity gradient)
+    let mut a = Generic2D::zeros((6, 6));
+    a.slice_mut(s![0..3, 3..6]).assign(&Generic2D::eye(3));
+    a.slice_mut(s![3..6, 0..3]).assign(&gravity_gradient(&r0));
+    let phi = Generic2D::eye(6) + &a * dt + a.dot(&a) * (dt * dt / 2.);
+
+    // Process noise (white acceleration)
+    let sa2 = arch.sigma_orbit_accel.powi(2);
+    let mut q = Generic2D::zeros((6, 6));
+    q.slice_mut(s![0..3, 0..3])
+        .assign(&(Generic2D::eye(3) * (sa2 * dt.powi(3) / 3.)));
+    q.slice_mut(s![0..3, 3..6])
+        .assign(&(Generic2D::eye(3) * (sa2 * dt.powi(2) / 2.)));
+    q.slice_mut(s![3..6, 0..3])
+        .assign(&(Generic2D::eye(3) * (sa2 * dt.powi(2) / 2.)));
+    q.slice_mut(s![3..6, 3..6])
+        .assign(&(Generic2D::eye(3) * (sa2 * dt)));
+
+    let cov = phi.dot(&est.cov_orbit).dot(&phi.t()) + q;
+    est.cov_orbit = (&cov + &cov.t()) / 2.;
+    est.t_since_fix += dt;
+}
+
+// Returns false if the measurement could not be fused
+pub fn update(
+    est: &mut EstimationBus,
+    r_meas: &Generic1D,
+    v_meas: &Generic1D,
+    arch: &EstimationArchitecture,
+) -> bool {
+    // Residual; H = I6
+    let z = concatenate![Axis(0), r_meas - &est.r_eci, v_meas - &est.v_eci];
+
+    // Gain
+    let r = measurement_noise(arch);
+    let Some(innov_cov_inv) = inv(&(&est.cov_orbit + &r)) else {
+        log::warn!("Orbit EKF innovation covariance singular; skipping GPSR update");
+        return false;
+    };
+    let k = est.cov_orbit.dot(&innov_cov_inv);
+    let dx = k.dot(&z);
+
+    // Covariance (Joseph form)
+    let ikh = Generic2D::eye(6) - &k;
+    let cov = ikh.dot(&est.cov_orbit).dot(&ikh.t()) + k.dot(&r).dot(&k.t());
+    est.cov_orbit = (&cov + &cov.t()) / 2.;
+
+    est.r_eci = &est.r_eci + &dx.slice(s![0..3]);
+    est.v_eci = &est.v_eci + &dx.slice(s![3..6]);
+    est.t_since_fix = 0.;
+
+    true
+}
+
+// RK4 step of two-body + J2 dynamics
+pub fn propagate_state(r_eci: &Generic1D, v_eci: &Generic1D, dt: f64) -> (Generic1D, Generic1D) {
+    let x0 = concatenate![Axis(0), *r_eci, *v_eci];
+    let k1 = dynamics(&x0);
+    let k2 = dynamics(&(&x0 + &k1 * (dt / 2.)));
+    let k3 = dynamics(&(&x0 + &k2 * (dt / 2.)));
+    let k4 = dynamics(&(&x0 + &k3 * dt));
+    let x1 = x0 + (k1 + k2 * 2. + k3 * 2. + k4) * (dt / 6.);
+    (x1.slice(s![0..3]).to_owned(), x1.slice(s![3..6]).to_owned())
+}
+
+// Gravitational acceleration (two-body + J2) in ECI
+pub fn gravity(r_eci: &Generic1D) -> Generic1D {
+    let r2 = r_eci.dot(r_eci);
+    let r = r2.sqrt();
+    let z2_r2 = r_eci[2].powi(2) / r2;
+
+    let a_2b = r_eci * (-MU_EARTH / r.powi(3));
+    let j2_fac = -1.5 * J2_EARTH * MU_EARTH * R_EARTH.powi(2) / r.powi(5);
+    let a_j2 = array![
+        j2_fac * r_eci[0] * (1. - 5. * z2_r2),
+        j2_fac * r_eci[1] * (1. - 5. * z2_r2),
+        j2_fac * r_eci[2] * (3. - 5. * z2_r2)
+    ];
+    a_2b + a_j2
+}
+
+fn dynamics(x: &Generic1D) -> Generic1D {
+    let r = x.slice(s![0..3]).to_owned();
+    let v = x.slice(s![3..6]).to_owned();
+    concatenate![Axis(0), v, gravity(&r)]
+}
+
+fn gravity_gradient(r_eci: &Generic1D) -> Generic2D {
+    let r = r_eci.dot(r_eci).sqrt();
+    let r_hat = r_eci / r;
+    let outer = r_hat
+        .view()
+        .insert_axis(Axis(1))
+        .dot(&r_hat.view().insert_axis(Axis(0)));
+    (outer * 3. - Generic2D::eye(3)) * (MU_EARTH / r.powi(3))
+}
+
+fn measurement_noise(arch: &EstimationArchitecture) -> Generic2D {
+    let mut r = Generic2D::zeros((6, 6));
+    r.slice_mut(s![0..3, 0..3])
+        .assign(&(Generic2D::eye(3) * arch.sigma_gpsr_pos.powi(2)));
+    r.slice_mut(s![3..6, 3..6])
+        .assign(&(Generic2D::eye(3) * arch.sigma_gpsr_vel.powi(2)));
+    r
+}
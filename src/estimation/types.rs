@@ -1,10 +1,189 @@
-use crate::sensors::types::SensorBus;
+use crate::estimation::{mekf, orbit};
+use crate::fsw_math::quat2dcm;
+use crate::fsw_types::ParamBus;
+use crate::scheduler::{Phase, RateGroup, Scheduler};
+use crate::sensors::types::{Sensor, SensorBus};
+use crate::time::TimeBus;
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::{array, s};
 
-#[derive(Clone, Debug, Default)]
-pub struct EstimationBus {}
+#[derive(Clone, Debug)]
+pub struct EstimationBus {
+    // Attitude (MEKF)
+    pub q_sc_eci: Generic1D,  // SC attitude wrt ECI
+    pub omega_sc: Generic1D,  // Bias-corrected rate in SC frame [rad/s]
+    pub gyro_bias: Generic1D, // Gyro bias in SC frame [rad/s]
+    pub cov_att: Generic2D,   // 6x6 error covariance [dtheta, dbias]
+    pub att_initialized: bool,
+    pub att_valid: bool,
+
+    // Orbit (EKF)
+    pub r_eci: Generic1D,     // SV position in ECI [m]
+    pub v_eci: Generic1D,     // SV velocity in ECI [m/s]
+    pub cov_orbit: Generic2D, // 6x6 error covariance [r, v]
+    pub t_since_fix: f64,     // Time since last fused GPSR fix [s]
+    pub orbit_initialized: bool,
+    pub orbit_valid: bool,
+
+    // Wheel Momentum (RWA TLM)
+    pub h_rw: Generic1D,    // Per-wheel momentum about spin axis [Nms]
+    pub h_rw_sc: Generic1D, // Total wheel momentum in SC frame [Nms]
+    pub h_rw_valid: bool,
+
+    // Sun
+    pub u_sun_eci: Generic1D, // Last measured sun unit vector in ECI
+    pub t_since_sun: f64,     // Time since last CSS sun vector [s]
+    pub sun_valid: bool,
+}
 
 impl EstimationBus {
-    pub fn process(_tlm_sensor: &SensorBus, _prev_est: &EstimationBus) -> Self {
-        Self {}
+    // Attitude, wheel momentum and sun; filter state already carried forward
+    pub fn process_attitude(
+        &mut self,
+        tlm_sensor: &SensorBus,
+        time_bus: &TimeBus,
+        param_bus: &ParamBus,
+    ) {
+        let arch = &param_bus.acs_estimation;
+
+        // Gather healthy measurements; STA only when processed this cycle
+        let gyro_sc = tlm_sensor
+            .imu_bus()
+            .filter(|imu| imu.hardware_subtest() == 0)
+            .map(|imu| imu.gyro_fused().to_owned());
+        let q_meas = tlm_sensor
+            .sta_bus()
+            .filter(|sta| sta.fresh(time_bus) && sta.hardware_subtest() == 0 && sta.fused_valid())
+            .map(|sta| (sta.q_fused().to_owned(), sta.cov_fused().to_owned()));
+
+        if !self.att_initialized {
+            if let Some((q_meas, _)) = &q_meas {
+                mekf::initialize(self, q_meas, arch);
+            }
+        } else {
+            // Hold last rate through IMU dropouts
+            let gyro = gyro_sc
+                .clone()
+                .unwrap_or_else(|| &self.omega_sc + &self.gyro_bias);
+            mekf::propagate(self, &gyro, time_bus.dt(), arch);
+
+            if let Some((q_meas, r_meas)) = &q_meas {
+                mekf::update(self, q_meas, r_meas);
+            }
+        }
+
+        let att_sigma = self
+            .cov_att
+            .slice(s![0..3, 0..3])
+            .diag()
+            .fold(0., |acc: f64, var| acc.max(*var))
+            .sqrt();
+        self.att_valid =
+            self.att_initialized && gyro_sc.is_some() && att_sigma < arch.sigma_att_valid;
+
+        // Wheel momentum; hold last through RWA TLM dropouts
+        let rwa = tlm_sensor
+            .rwa_bus()
+            .filter(|rwa| rwa.hardware_subtest() == 0);
+        if let Some(rwa) = rwa {
+            self.h_rw = rwa.h_rw().to_owned();
+            self.h_rw_sc = rwa.h_rw_sc().to_owned();
+        }
+        self.h_rw_valid = rwa.is_some();
+
+        // Sun; hold last CSS direction in ECI through eclipse, up to sun_age_max
+        if let Some(u_sun_sc) = tlm_sensor.u_sun_sc().filter(|_| self.att_valid) {
+            self.u_sun_eci = quat2dcm(&self.q_sc_eci).t().dot(&u_sun_sc);
+            self.t_since_sun = 0.;
+            self.sun_valid = true;
+        } else {
+            self.t_since_sun += time_bus.dt();
+            if self.sun_valid && self.t_since_sun > arch.sun_age_max {
+                log::warn!("Sun vector {:.0} s old; invalidated", self.t_since_sun);
+                self.sun_valid = false;
+            }
+        }
+    }
+
+    // Orbit; dt spans the orbit rate group period
+    pub fn process_orbit(
+        &mut self,
+        tlm_sensor: &SensorBus,
+        time_bus: &TimeBus,
+        param_bus: &ParamBus,
+    ) {
+        let arch = &param_bus.acs_estimation;
+        let gpsr = tlm_sensor.gpsr_bus().filter(|gpsr| gpsr.fresh(time_bus));
+        let rv_meas = gpsr
+            .filter(|gpsr| gpsr.hardware_subtest() == 0 && gpsr.fix_consistent())
+            .and_then(|gpsr| gpsr.best_fix());
+        let reinit = gpsr.is_some_and(|gpsr| gpsr.orbit_reinit());
+
+        // Coast through GPSR outages; restart from the fix if the estimate diverged
+        if !self.orbit_initialized || reinit {
+            if let Some((r_meas, v_meas)) = &rv_meas {
+                orbit::initialize(self, r_meas, v_meas, arch);
+            }
+        } else {
+            orbit::propagate(self, time_bus.dt(), arch);
+
+            if let Some((r_meas, v_meas)) = &rv_meas {
+                orbit::update(self, r_meas, v_meas, arch);
+            }
+        }
+
+        let pos_sigma = self
+            .cov_orbit
+            .slice(s![0..3, 0..3])
+            .diag()
+            .fold(0., |acc: f64, var| acc.max(*var))
+            .sqrt();
+        self.orbit_valid = self.orbit_initialized && pos_sigma < arch.sigma_pos_valid;
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        scheduler.register(
+            "ATTITUDE",
+            RateGroup::FAST,
+            Phase::ESTIMATION,
+            |curr, _, time_bus, param_bus| {
+                curr.estimation_bus
+                    .process_attitude(&curr.tlm_sensor_bus, time_bus, param_bus)
+            },
+        );
+        scheduler.register(
+            "ORBIT",
+            RateGroup::NAV,
+            Phase::ESTIMATION,
+            |curr, _, time_bus, param_bus| {
+                curr.estimation_bus
+                    .process_orbit(&curr.tlm_sensor_bus, time_bus, param_bus)
+            },
+        );
+    }
+}
+
+impl Default for EstimationBus {
+    fn default() -> Self {
+        Self {
+            q_sc_eci: array![0., 0., 0., 1.],
+            omega_sc: Generic1D::zeros(3),
+            gyro_bias: Generic1D::zeros(3),
+            cov_att: Generic2D::zeros((6, 6)),
+            att_initialized: false,
+            att_valid: false,
+            r_eci: Generic1D::zeros(3),
+            v_eci: Generic1D::zeros(3),
+            cov_orbit: Generic2D::zeros((6, 6)),
+            t_since_fix: 0.,
+            orbit_initialized: false,
+            orbit_valid: false,
+            h_rw: Generic1D::zeros(0),
+            h_rw_sc: Generic1D::zeros(3),
+            h_rw_valid: false,
+            u_sun_eci: Generic1D::zeros(3),
+            t_since_sun: 0.,
+            sun_valid: false,
+        }
     }
 }
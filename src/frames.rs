@@ -0,0 +1,196 @@
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use ndarray::array;
+
+use crate::time::{self, GpsTime, LeapSeconds};
+
+// ECEF (ITRF) <-> ECI (J2000 / FK5) per IAU-76/FK5 (Vallado, 4th ed., sec 3.7)
+// r_eci = P N R W r_ecef
+//   W: polar motion        (ITRF -> PEF)
+//   R: sidereal rotation   (PEF  -> TOD)
+//   N: IAU-1980 nutation   (TOD  -> MOD); leading 18 terms, few mas
+//   P: IAU-1976 precession (MOD  -> J2000)
+// Reference: Vallado Example 3-15 (2004-04-06 07:51:28.386009 UTC); ~8 cm, ~0.5 mm/s
+//   Truncated nutation (18 of 106 terms) otherwise bounds accuracy to sub-metre in LEO
+
+const ARCSEC: f64 = std::f64::consts::PI / (180. * 3600.);
+const OMEGA_EARTH: f64 = 7.292115146706979e-5; // Nominal Earth rotation rate [rad/s]
+const JD_J2000: f64 = 2451545.0;
+
+// Earth orientation parameters (IERS Bulletin A/B); uploaded from ground
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EarthOrientation {
+    pub xp: f64,    // Polar motion x [rad]
+    pub yp: f64,    // Polar motion y [rad]
+    pub dut1: f64,  // UT1 - UTC [s]
+    pub lod: f64,   // Excess length of day [s]
+    pub ddpsi: f64, // Nutation correction in longitude wrt IAU-1980 [rad]
+    pub ddeps: f64, // Nutation correction in obliquity wrt IAU-1980 [rad]
+}
+
+// Nutation series; multipliers of (l, l', F, D, Omega), dpsi = (a + b T) sin, deps = (c + d T) cos [0.0001"]
+const NUT_1980: [([f64; 5], [f64; 4]); 18] = [
+    ([0., 0., 0., 0., 1.], [-171996., -174.2, 92025., 8.9]),
+    ([0., 0., 2., -2., 2.], [-13187., -1.6, 5736., -3.1]),
+    ([0., 0., 2., 0., 2.], [-2274., -0.2, 977., -0.5]),
+    ([0., 0., 0., 0., 2.], [2062., 0.2, -895., 0.5]),
+    ([0., 1., 0., 0., 0.], [1426., -3.4, 54., -0.1]),
+    ([1., 0., 0., 0., 0.], [712., 0.1, -7., 0.]),
+    ([0., 1., 2., -2., 2.], [-517., 1.2, 224., -0.6]),
+    ([0., 0., 2., 0., 1.], [-386., -0.4, 200., 0.]),
+    ([1., 0., 2., 0., 2.], [-301., 0., 129., -0.1]),
+    ([0., -1., 2., -2., 2.], [217., -0.5, -95., 0.3]),
+    ([1., 0., 0., -2., 0.], [-158., 0., -1., 0.]),
+    ([0., 0., 2., -2., 1.], [129., 0.1, -70., 0.]),
+    ([-1., 0., 2., 0., 2.], [123., 0., -53., 0.]),
+    ([1., 0., 0., 0., 1.], [63., 0.1, -33., 0.]),
+    ([0., 0., 0., 2., 0.], [63., 0., -2., 0.]),
+    ([-1., 0., 2., 2., 2.], [-59., 0., 26., 0.]),
+    ([-1., 0., 0., 0., 1.], [-58., -0.1, 32., 0.]),
+    ([1., 0., 2., 0., 1.], [-51., 0., 27., 0.]),
+];
+
+// ECEF -> ECI rotation
+pub fn ecef2eci_dcm(jd_tt: f64, jd_ut1: f64, eop: &EarthOrientation) -> Generic2D {
+    let (prec, nut, sid, pm, _) = fk5_rotations(jd_tt, jd_ut1, eop);
+    prec.dot(&nut).dot(&sid).dot(&pm)
+}
+
+// Julian dates (TT, UT1) at GPS time
+pub fn gps2jd(gps: &GpsTime, leap: &LeapSeconds, eop: &EarthOrientation) -> (f64, f64) {
+    let tai = time::gps2tai(gps.to_seconds());
+    let utc = time::tai2utc(tai, leap);
+    (time::jd(time::tai2tt(tai)), time::jd(utc + eop.dut1))
+}
+
+// ECEF position/velocity -> ECI
+pub fn ecef2eci(
+    r_ecef: &Generic1D,
+    v_ecef: &Generic1D,
+    jd_tt: f64,
+    jd_ut1: f64,
+    eop: &EarthOrientation,
+) -> (Generic1D, Generic1D) {
+    let (prec, nut, sid, pm, omega) = fk5_rotations(jd_tt, jd_ut1, eop);
+    let pn_sid = prec.dot(&nut).dot(&sid);
+
+    // Transport term w/ Earth rate in PEF
+    let r_pef = pm.dot(r_ecef);
+    let v_pef = pm.dot(v_ecef) + array![-omega * r_pef[1], omega * r_pef[0], 0.];
+
+    (pn_sid.dot(&r_pef), pn_sid.dot(&v_pef))
+}
+
+// (P, N, R, W, Earth rate)
+fn fk5_rotations(
+    jd_tt: f64,
+    jd_ut1: f64,
+    eop: &EarthOrientation,
+) -> (Generic2D, Generic2D, Generic2D, Generic2D, f64) {
+    let ttt = (jd_tt - JD_J2000) / 36525.;
+    let tut1 = (jd_ut1 - JD_J2000) / 36525.;
+
+    // Precession (IAU-1976)
+    let zeta = (2306.2181 * ttt + 0.30188 * ttt.powi(2) + 0.017998 * ttt.powi(3)) * ARCSEC;
+    let theta = (2004.3109 * ttt - 0.42665 * ttt.powi(2) - 0.041833 * ttt.powi(3)) * ARCSEC;
+    let z = (2306.2181 * ttt + 1.09468 * ttt.powi(2) + 0.018203 * ttt.powi(3)) * ARCSEC;
+    let prec = rot3(zeta).dot(&rot2(-theta)).dot(&rot3(z));
+
+    // Nutation (IAU-1980)
+    let (dpsi, deps, eps_bar, omega_moon) = nutation(ttt);
+    let dpsi = dpsi + eop.ddpsi;
+    let eps = eps_bar + deps + eop.ddeps;
+    let nut = rot1(-eps_bar).dot(&rot3(dpsi)).dot(&rot1(eps));
+
+    // Sidereal time (IAU-1982 GMST + equation of equinoxes)
+    let gmst_sec =
+        67310.54841 + (876600. * 3600. + 8640184.812866) * tut1 + 0.093104 * tut1.powi(2)
+            - 6.2e-6 * tut1.powi(3);
+    let gmst = (gmst_sec % 86400.) / 240. * std::f64::consts::PI / 180.;
+    let eqe = dpsi * eps_bar.cos()
+        + (0.00264 * omega_moon.sin() + 0.000063 * (2. * omega_moon).sin()) * ARCSEC;
+    let gast = gmst + eqe;
+    let sid = rot3(-gast);
+
+    // Polar motion
+    let pm = rot1(eop.yp).dot(&rot2(eop.xp));
+
+    let omega = OMEGA_EARTH * (1. - eop.lod / 86400.);
+    (prec, nut, sid, pm, omega)
+}
+
+// (dpsi, deps, mean obliquity, lunar node) [rad]
+fn nutation(ttt: f64) -> (f64, f64, f64, f64) {
+    let rev = 1296000.;
+    let args = [
+        485866.733 + (1325. * rev + 715922.633) * ttt + 31.310 * ttt.powi(2) + 0.064 * ttt.powi(3),
+        1287099.804 + (99. * rev + 1292581.224) * ttt - 0.577 * ttt.powi(2) - 0.012 * ttt.powi(3),
+        335778.877 + (1342. * rev + 295263.137) * ttt - 13.257 * ttt.powi(2) + 0.011 * ttt.powi(3),
+        1072261.307 + (1236. * rev + 1105601.328) * ttt - 6.891 * ttt.powi(2) + 0.019 * ttt.powi(3),
+        450160.280 - (5. * rev + 482890.539) * ttt + 7.455 * ttt.powi(2) + 0.008 * ttt.powi(3),
+    ]
+    .map(|arg| (arg % rev) * ARCSEC);
+
+    let (dpsi, deps) = NUT_1980
+        .iter()
+        .fold((0., 0.), |(dpsi, deps), (mult, coef)| {
+            let arg: f64 = mult.iter().zip(args.iter()).map(|(m, a)| m * a).sum();
+            (
+                dpsi + (coef[0] + coef[1] * ttt) * arg.sin(),
+                deps + (coef[2] + coef[3] * ttt) * arg.cos(),
+            )
+        });
+
+    let eps_bar =
+        (84381.448 - 46.8150 * ttt - 0.00059 * ttt.powi(2) + 0.001813 * ttt.powi(3)) * ARCSEC;
+    (dpsi * 1e-4 * ARCSEC, deps * 1e-4 * ARCSEC, eps_bar, args[4])
+}
+
+// Frame rotations about x, y, z
+fn rot1(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[1., 0., 0.], [0., c, s], [0., -s, c]]
+}
+
+fn rot2(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[c, 0., -s], [0., 1., 0.], [s, 0., c]]
+}
+
+fn rot3(angle: f64) -> Generic2D {
+    let (s, c) = angle.sin_cos();
+    array![[c, s, 0.], [-s, c, 0.], [0., 0., 1.]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vallado Example 3-15; ECEF -> J2000 w/ EOP nutation corrections
+    #[test]
+    fn ecef2eci_vallado_3_15() {
+        let eop = EarthOrientation {
+            xp: -0.140682 * ARCSEC,
+            yp: 0.333309 * ARCSEC,
+            dut1: -0.4399619,
+            lod: 0.0015563,
+            ddpsi: -0.052195 * ARCSEC,
+            ddeps: -0.003875 * ARCSEC,
+        };
+
+        // 2004-04-06 07:51:28.386009 UTC; GPS - UTC = 13 s
+        let gps = GpsTime::new(1265, 201101.386009);
+        let (jd_tt, jd_ut1) = gps2jd(&gps, &LeapSeconds::default(), &eop);
+
+        let r_ecef = array![-1033.4793830, 7901.2952754, 6380.3565958] * 1e3;
+        let v_ecef = array![-3.225636520, -2.872451450, 5.531924446] * 1e3;
+        let (r_eci, v_eci) = ecef2eci(&r_ecef, &v_ecef, jd_tt, jd_ut1, &eop);
+
+        // Tolerance covers the truncated nutation series
+        let r_exp = array![5102.508958, 6123.011401, 6378.136928] * 1e3;
+        let v_exp = array![-4.74322016, 0.79053650, 5.53375528] * 1e3;
+        let dr = &r_eci - &r_exp;
+        let dv = &v_eci - &v_exp;
+        assert!(dr.dot(&dr).sqrt() < 0.2, "position error {:?} m", dr);
+        assert!(dv.dot(&dv).sqrt() < 1e-3, "velocity error {:?} m/s", dv);
+    }
+}
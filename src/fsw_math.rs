@@ -0,0 +1,170 @@
+use altai_rs::meta::types::{Generic1D, Generic2D};
+use altai_rs::veclib::mfcross;
+use ndarray::{array, s, Axis};
+use std::f64::consts::{PI, TAU};
+
+// Quaternions are scalar-last [q1, q2, q3, q4] and composed such that
+// A(q ⊗ p) = A(q) A(p), where A(q) maps vectors from the reference frame
+// into the body frame.
+
+pub fn qmult(q: &Generic1D, p: &Generic1D) -> Generic1D {
+    let qv = q.slice(s![0..3]).to_owned();
+    let pv = p.slice(s![0..3]).to_owned();
+    let vec = &pv * q[3] + &qv * p[3] - mfcross(&qv, &pv);
+    array![vec[0], vec[1], vec[2], q[3] * p[3] - qv.dot(&pv)]
+}
+
+pub fn qconj(q: &Generic1D) -> Generic1D {
+    array![-q[0], -q[1], -q[2], q[3]]
+}
+
+pub fn qnormalize(q: &Generic1D) -> Generic1D {
+    let norm = q.dot(q).sqrt();
+    if norm < f64::EPSILON {
+        return array![0., 0., 0., 1.];
+    }
+    q / norm
+}
+
+// Resolve the q / -q ambiguity toward the positive scalar part
+pub fn qpositive(q: &Generic1D) -> Generic1D {
+    if q[3] < 0. {
+        -q
+    } else {
+        q.to_owned()
+    }
+}
+
+// Error quaternion dq = q ⊗ q_ref^-1 (shortest path)
+pub fn qerror(q: &Generic1D, q_ref: &Generic1D) -> Generic1D {
+    qpositive(&qmult(q, &qconj(q_ref)))
+}
+
+pub fn quat2dcm(q: &Generic1D) -> Generic2D {
+    let qv = q.slice(s![0..3]).to_owned();
+    let outer = qv
+        .view()
+        .insert_axis(Axis(1))
+        .dot(&qv.view().insert_axis(Axis(0)));
+    Generic2D::eye(3) * (q[3] * q[3] - qv.dot(&qv)) + outer * 2. - skew(&qv) * (2. * q[3])
+}
+
+pub fn skew(v: &Generic1D) -> Generic2D {
+    array![[0., -v[2], v[1]], [v[2], 0., -v[0]], [-v[1], v[0], 0.]]
+}
+
+// Rotation vector -> quaternion (exponential map)
+pub fn rotvec2quat(phi: &Generic1D) -> Generic1D {
+    let angle = phi.dot(phi).sqrt();
+    if angle < 1e-12 {
+        return qnormalize(&array![phi[0] / 2., phi[1] / 2., phi[2] / 2., 1.]);
+    }
+    let axis = phi / angle;
+    let sin = (angle / 2.).sin();
+    array![
+        axis[0] * sin,
+        axis[1] * sin,
+        axis[2] * sin,
+        (angle / 2.).cos()
+    ]
+}
+
+// Quaternion -> rotation vector (logarithmic map, shortest path)
+pub fn quat2rotvec(q: &Generic1D) -> Generic1D {
+    let q = qpositive(q);
+    let qv = q.slice(s![0..3]).to_owned();
+    let sin = qv.dot(&qv).sqrt();
+    if sin < 1e-12 {
+        return qv * 2.;
+    }
+    let angle = 2. * sin.atan2(q[3]);
+    qv * (angle / sin)
+}
+
+// Wrap to (-pi, pi]
+pub fn wrap_angle(angle: f64) -> f64 {
+    PI - (PI - angle).rem_euclid(TAU)
+}
+
+// Gauss-Jordan inverse w/ partial pivoting; None if singular
+pub fn inv(m: &Generic2D) -> Option<Generic2D> {
+    let n = m.nrows();
+    let mut a = m.to_owned();
+    let mut b = Generic2D::eye(n);
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[[i, col]].abs().total_cmp(&a[[j, col]].abs()))
+            .unwrap();
+        if a[[pivot, col]].abs() < 1e-14 {
+            return None;
+        }
+        for k in 0..n {
+            a.swap([col, k], [pivot, k]);
+            b.swap([col, k], [pivot, k]);
+        }
+
+        let diag = a[[col, col]];
+        a.row_mut(col).mapv_inplace(|x| x / diag);
+        b.row_mut(col).mapv_inplace(|x| x / diag);
+
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = a[[row, col]];
+            if factor == 0. {
+                continue;
+            }
+            let a_col = a.row(col).to_owned();
+            let b_col = b.row(col).to_owned();
+            a.row_mut(row).scaled_add(-factor, &a_col);
+            b.row_mut(row).scaled_add(-factor, &b_col);
+        }
+    }
+    Some(b)
+}
+
+// Symmetric eigendecomposition (cyclic Jacobi); eigenvectors are the columns
+pub fn sym_eig(m: &Generic2D) -> (Generic1D, Generic2D) {
+    let n = m.nrows();
+    let mut a = m.to_owned();
+    let mut v = Generic2D::eye(n);
+
+    for _sweep in 0..50 {
+        let off = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .fold(0., |acc, (i, j)| acc + a[[i, j]].powi(2));
+        if off < 1e-30 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[[p, q]].abs() < 1e-300 {
+                    continue;
+                }
+                // Rotation zeroing a[p, q]
+                let theta = (a[[q, q]] - a[[p, p]]) / (2. * a[[p, q]]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[[k, p]], a[[k, q]]);
+                    a[[k, p]] = c * akp - s * akq;
+                    a[[k, q]] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[[p, k]], a[[q, k]]);
+                    a[[p, k]] = c * apk - s * aqk;
+                    a[[q, k]] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[[k, p]], v[[k, q]]);
+                    v[[k, p]] = c * vkp - s * vkq;
+                    v[[k, q]] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    (a.diag().to_owned(), v)
+}
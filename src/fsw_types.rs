@@ -1,15 +1,24 @@
-use altai_rs::types::{Generic2D, Quaternion4};
+use altai_rs::types::{Generic1D, Generic2D, Quaternion4};
+use ndarray::array;
 
 use crate::{
     actuators::types::ActuatorBus,
     control::types::ControlBus,
     estimation::types::EstimationBus,
+    frames::EarthOrientation,
     reference::types::ReferenceBus,
-    sensors::types::{RawSensorBus, SensorBus},
+    scheduler::SchedulerArchitecture,
+    sensors::{
+        fdir::{BitPersistence, FdirConfig},
+        timing::SensorClock,
+        types::{RawSensorBus, SensorBus},
+    },
+    time::{TimeArchitecture, TimeBus},
 };
 
 #[derive(Clone, Debug, Default)]
 pub struct GNCState {
+    pub time_bus: TimeBus,
     pub raw_sensor_bus: RawSensorBus,
     pub tlm_sensor_bus: SensorBus,
     pub estimation_bus: EstimationBus,
@@ -19,8 +28,13 @@ pub struct GNCState {
 }
 pub trait Param {}
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
 pub struct ParamBus {
+    pub gnc_dt: f64,           // Nominal GNC cycle period [s]
+    pub eop: EarthOrientation, // Earth orientation for ECEF <-> ECI
+    pub time: TimeArchitecture,
+    pub scheduler: SchedulerArchitecture,
+
     pub acs_sensors: SensorArchitecture,
     pub acs_estimation: EstimationArchitecture,
     pub acs_reference: ReferenceArchitecture,
@@ -31,6 +45,7 @@ pub struct ParamBus {
 }
 impl ParamBus {
     pub fn initialize(
+        gnc_dt: f64,
         acs_sensors: SensorArchitecture,
         acs_estimation: EstimationArchitecture,
         acs_reference: ReferenceArchitecture,
@@ -39,6 +54,10 @@ impl ParamBus {
         acs_multibody: MultibodyArchitecture,
     ) -> Self {
         Self {
+            gnc_dt,
+            eop: EarthOrientation::default(),
+            time: TimeArchitecture::default(),
+            scheduler: SchedulerArchitecture::initialize(gnc_dt),
             acs_sensors,
             acs_estimation,
             acs_reference,
@@ -49,26 +68,269 @@ impl ParamBus {
     }
 }
 
-#[derive(Clone, Default, Debug)]
-pub struct ActuatorArchitecture {}
+impl Default for ParamBus {
+    fn default() -> Self {
+        Self {
+            gnc_dt: 0.1,
+            eop: EarthOrientation::default(),
+            time: TimeArchitecture::default(),
+            scheduler: SchedulerArchitecture::default(),
+            acs_sensors: SensorArchitecture::default(),
+            acs_estimation: EstimationArchitecture::default(),
+            acs_reference: ReferenceArchitecture::default(),
+            acs_control: ControlArchitecture::default(),
+            acs_actuators: ActuatorArchitecture::default(),
+            acs_multibody: MultibodyArchitecture::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ActuatorArchitecture {
+    // RWA
+    pub rw_axes: Generic2D,   // 3xN wheel spin axes in SC frame
+    pub rw_torque_max: f64,   // Max wheel torque [Nm]
+    pub rw_momentum_max: f64, // Max wheel momentum [Nms]
+    pub rw_inertia: f64,      // Wheel inertia about spin axis [kg*m^2]
+
+    // MTQ; one torquer per SC axis
+    pub mtq_dipole_max: f64, // Max dipole per torquer [Am^2]
+
+    // SADA
+    pub sada_axes: Generic2D,    // 3xN array rotation axes in SC frame
+    pub sada_normals: Generic2D, // 3xN array normals at zero angle in SC frame
+    pub sada_angle_min: f64,     // Hard stop [rad]
+    pub sada_angle_max: f64,     // Hard stop [rad]
+    pub sada_rate_max: f64,      // Max array rate [rad/s]
+    pub sada_park_angle: f64,    // Park position [rad]
+}
+impl ActuatorArchitecture {
+    pub fn initialize(
+        rw_axes: &Generic2D,
+        rw_torque_max: f64,
+        rw_momentum_max: f64,
+        rw_inertia: f64,
+        mtq_dipole_max: f64,
+    ) -> Self {
+        Self {
+            rw_axes: rw_axes.to_owned(),
+            rw_torque_max,
+            rw_momentum_max,
+            rw_inertia,
+            mtq_dipole_max,
+            sada_axes: Generic2D::zeros((3, 0)),
+            sada_normals: Generic2D::zeros((3, 0)),
+            sada_angle_min: -std::f64::consts::PI,
+            sada_angle_max: std::f64::consts::PI,
+            sada_rate_max: 1f64.to_radians(),
+            sada_park_angle: 0.,
+        }
+    }
+
+    pub fn n_rw(&self) -> usize {
+        self.rw_axes.ncols()
+    }
+
+    pub fn n_sada(&self) -> usize {
+        self.sada_axes.ncols()
+    }
+}
+impl Default for ActuatorArchitecture {
+    fn default() -> Self {
+        Self::initialize(&Generic2D::eye(3), 0.01, 0.1, 1e-4, 0.2)
+    }
+}
 impl Param for ActuatorArchitecture {}
 
-#[derive(Clone, Default, Debug)]
-pub struct ControlArchitecture {}
+#[derive(Clone, Debug)]
+pub struct ControlArchitecture {
+    // Attitude PID
+    pub kp: Generic1D,    // Proportional gain per SC axis [Nm/rad]
+    pub kd: Generic1D,    // Derivative gain per SC axis [Nm/(rad/s)]
+    pub ki: Generic1D,    // Integral gain per SC axis; zero to disable [Nm/(rad*s)]
+    pub int_limit: f64,   // Integrator clamp per axis [rad*s]
+    pub int_err_max: f64, // Integrate only below this attitude error [rad]
+    pub torque_max: f64,  // Max commanded torque per axis [Nm]
+
+    // Detumble
+    pub bdot_gain: f64, // B-dot rate damping gain [Nms]
+
+    // SADA
+    pub sada_gain: f64, // Angle tracking gain [1/s]
+}
+impl Default for ControlArchitecture {
+    fn default() -> Self {
+        Self {
+            kp: Generic1D::from_elem(3, 0.01),
+            kd: Generic1D::from_elem(3, 0.1),
+            ki: Generic1D::zeros(3),
+            int_limit: 1.,
+            int_err_max: 5f64.to_radians(),
+            torque_max: 0.01,
+            bdot_gain: 1e-2,
+            sada_gain: 0.1,
+        }
+    }
+}
 impl Param for ControlArchitecture {}
 
-#[derive(Clone, Default, Debug)]
-pub struct EstimationArchitecture {}
+#[derive(Clone, Debug)]
+pub struct EstimationArchitecture {
+    // Attitude MEKF
+    pub sigma_gyro_arw: f64,  // Gyro angle random walk [rad/s^0.5]
+    pub sigma_gyro_rrw: f64,  // Gyro rate random walk [rad/s^1.5]
+    pub sigma_sta: f64,       // STA noise per axis (1-sigma) [rad]
+    pub sigma_att_init: f64,  // Initial attitude uncertainty (1-sigma) [rad]
+    pub sigma_bias_init: f64, // Initial gyro bias uncertainty (1-sigma) [rad/s]
+    pub sigma_att_valid: f64, // Max attitude uncertainty for a valid estimate (1-sigma) [rad]
+
+    // Orbit EKF
+    pub sigma_gpsr_pos: f64,    // GPSR position noise per axis (1-sigma) [m]
+    pub sigma_gpsr_vel: f64,    // GPSR velocity noise per axis (1-sigma) [m/s]
+    pub sigma_orbit_accel: f64, // Unmodeled acceleration (1-sigma) [m/s^2]
+    pub sigma_pos_valid: f64,   // Max position uncertainty for a valid estimate (1-sigma) [m]
+
+    // Sun
+    pub sun_age_max: f64, // Hold last sun vector through eclipse up to [s]
+}
+impl Default for EstimationArchitecture {
+    fn default() -> Self {
+        Self {
+            sigma_gyro_arw: 1e-5,
+            sigma_gyro_rrw: 1e-8,
+            sigma_sta: 5e-5,
+            sigma_att_init: 1e-2,
+            sigma_bias_init: 1e-3,
+            sigma_att_valid: 5e-3,
+            sigma_gpsr_pos: 10.,
+            sigma_gpsr_vel: 0.1,
+            sigma_orbit_accel: 1e-5,
+            sigma_pos_valid: 1000.,
+            sun_age_max: 3600.,
+        }
+    }
+}
 impl Param for EstimationArchitecture {}
 
-#[derive(Clone, Default, Debug)]
-pub struct ReferenceArchitecture {}
+#[derive(Clone, Debug)]
+pub struct ReferenceArchitecture {
+    // Slew
+    pub slew_rate_max: f64,  // Max eigenaxis rate [rad/s]
+    pub slew_accel_max: f64, // Max eigenaxis accel [rad/s^2]
+}
+impl Default for ReferenceArchitecture {
+    fn default() -> Self {
+        Self {
+            slew_rate_max: 1f64.to_radians(),
+            slew_accel_max: 0.1f64.to_radians(),
+        }
+    }
+}
 impl Param for ReferenceArchitecture {}
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
 pub struct SensorArchitecture {
     pub q_sc_imu: Quaternion4,
-    pub q_sc_sta: Quaternion4,
+    pub q_sc_sta: Quaternion4, // 4xN per-head mount; one column shared by every head
+    pub q_sc_mag: Quaternion4,
+
+    // IMU FDIR
+    pub imu_axes: Generic2D, // 3x(3N) per-axis sense directions in SC frame; empty to use q_sc_imu
+    pub imu_gyro_sigma: f64, // Gyro noise per axis (1-sigma) [rad/s]
+    pub imu_accel_sigma: f64, // Accel noise per axis (1-sigma) [m/s^2]
+    pub imu_fdi_k: f64,      // Parity residual threshold [sigma]
+
+    // STA FDIR
+    pub sta_ang_max: f64, // Max angle from propagated estimate [rad]
+
+    // GPSR FDIR
+    pub gpsr_pos_tol: f64,  // Max position diff from propagated estimate [m]
+    pub gpsr_vel_tol: f64,  // Max velocity diff from propagated estimate [m/s]
+    pub gpsr_alt_min: f64,  // Min plausible altitude [m]
+    pub gpsr_alt_max: f64,  // Max plausible altitude [m]
+    pub gpsr_sats_min: u32, // Min satellites for a usable fix
+    pub gpsr_reinit_n: u32, // Consecutive off-estimate checks before orbit re-init
+
+    // MAG calibration; b_cal = S * (b_raw - h)
+    pub mag_hard_iron: Generic1D, // h in MAG frame [nT]
+    pub mag_soft_iron: Generic2D, // S in MAG frame
+    pub mag_b_max: f64,           // Max expected field magnitude [T]
+
+    // CSS
+    pub css_normals: Generic2D, // 3xN photodiode boresight normals in SC frame
+    pub css_scale: Generic1D,   // Per-photodiode current at normal incidence [A]
+    pub css_threshold: f64,     // Normalized response below which albedo/noise is assumed
+
+    // RWA TLM
+    pub rwa_temp_max: f64, // Max motor temperature [degC]
+
+    // SADA
+    pub sada_resolver_counts: u32, // Resolver counts per revolution
+    pub sada_resolver_offset: f64, // Resolver angle at array zero [rad]
+
+    // Sensor clock correlation per sensor
+    pub imu_clock: SensorClock,
+    pub sta_clock: SensorClock,
+    pub gpsr_clock: SensorClock,
+    pub mag_clock: SensorClock,
+    pub css_clock: SensorClock,
+    pub rwa_clock: SensorClock,
+    pub sada_clock: SensorClock,
+
+    // Health bit persistence / latching per sensor
+    pub imu_fdir: FdirConfig,
+    pub sta_fdir: FdirConfig,
+    pub gpsr_fdir: FdirConfig,
+    pub mag_fdir: FdirConfig,
+    pub css_fdir: FdirConfig,
+    pub rwa_fdir: FdirConfig,
+    pub sada_fdir: FdirConfig,
+}
+impl Default for SensorArchitecture {
+    fn default() -> Self {
+        Self {
+            q_sc_imu: array![[0.], [0.], [0.], [1.]],
+            q_sc_sta: array![[0.], [0.], [0.], [1.]],
+            q_sc_mag: array![[0.], [0.], [0.], [1.]],
+            imu_axes: Generic2D::zeros((3, 0)),
+            imu_gyro_sigma: 1e-4,
+            imu_accel_sigma: 1e-2,
+            imu_fdi_k: 5.,
+            sta_ang_max: 10f64.to_radians(),
+            gpsr_pos_tol: 1000.,
+            gpsr_vel_tol: 10.,
+            gpsr_alt_min: 100e3,
+            gpsr_alt_max: 37000e3,
+            gpsr_sats_min: 4,
+            gpsr_reinit_n: 10,
+            mag_hard_iron: Generic1D::zeros(3),
+            mag_soft_iron: Generic2D::eye(3),
+            mag_b_max: 1e-4,
+            css_normals: Generic2D::zeros((3, 0)),
+            css_scale: Generic1D::zeros(0),
+            css_threshold: 0.1,
+            rwa_temp_max: 70.,
+            sada_resolver_counts: 1 << 16,
+            sada_resolver_offset: 0.,
+            imu_clock: SensorClock::default(),
+            sta_clock: SensorClock::default(),
+            gpsr_clock: SensorClock::default(),
+            mag_clock: SensorClock::default(),
+            css_clock: SensorClock::default(),
+            rwa_clock: SensorClock::default(),
+            sada_clock: SensorClock::default(),
+            imu_fdir: FdirConfig::default(),
+            sta_fdir: FdirConfig::default(),
+            gpsr_fdir: FdirConfig::default()
+                .with_bit(4, BitPersistence::new(3, 3, 1, false))
+                .with_bit(5, BitPersistence::new(3, 3, 1, false))
+                .with_bit(6, BitPersistence::new(3, 3, 1, false)),
+            mag_fdir: FdirConfig::default(),
+            css_fdir: FdirConfig::default(),
+            rwa_fdir: FdirConfig::default(),
+            sada_fdir: FdirConfig::default(),
+        }
+    }
 }
 impl Param for SensorArchitecture {}
 
@@ -1,87 +1,112 @@
 pub mod actuators;
 pub mod control;
 pub mod estimation;
+pub mod frames;
+pub mod fsw_math;
 pub mod fsw_types;
 pub mod reference;
+pub mod scheduler;
 pub mod sensors;
+pub mod time;
 
 use actuators::types::ActuatorBus;
+use control::types::ControlBus;
+use estimation::types::EstimationBus;
+use frames::EarthOrientation;
 use fsw_types::{GNCState, ParamBus};
-use sensors::types::RawSensorBus;
-
-use log;
+use reference::types::ReferenceBus;
+use scheduler::Scheduler;
+use sensors::types::{RawSensorBus, SensorBus, SensorHealthTlm};
+use time::{LeapSeconds, TimeBus};
 
 #[derive(Debug)]
 pub struct FlightSoftware {
     param_bus: ParamBus,
+    scheduler: Scheduler,
     prev_state: GNCState,
     pub curr_state: GNCState,
 }
 
 impl FlightSoftware {
     // Initialize FSW / Consts
-    pub fn initialize(fsw_params: ParamBus) -> Self {
+    pub fn initialize(mut fsw_params: ParamBus) -> Self {
         log::trace!("Initializing FSW");
+
+        // FAST must run every cycle; cycle_dt consumers assume it
+        let fast = &mut fsw_params.scheduler.fast;
+        if fast.period != fsw_params.gnc_dt {
+            log::error!(
+                "FAST period {} s does not match GNC period {} s; using GNC period",
+                fast.period,
+                fsw_params.gnc_dt
+            );
+            fast.period = fsw_params.gnc_dt;
+        }
+
+        // Subsystems register their own tasks / rate groups
+        let mut scheduler = Scheduler::default();
+        SensorBus::register(&mut scheduler);
+        TimeBus::register(&mut scheduler);
+        EstimationBus::register(&mut scheduler);
+        ReferenceBus::register(&mut scheduler);
+        ControlBus::register(&mut scheduler);
+        ActuatorBus::register(&mut scheduler);
+
         Self {
             param_bus: fsw_params,
+            scheduler,
             prev_state: GNCState::default(),
             curr_state: GNCState::default(),
         }
     }
 
     // "GNC Loop" -> outputs Actuator Commands
-    pub fn gnc_loop(&mut self, raw_sensor_bus: &mut RawSensorBus) -> ActuatorBus {
+    // fsw_time: FSW clock at cycle start [s]; dt is derived from it and validated
+    pub fn gnc_loop(&mut self, fsw_time: f64, raw_sensor_bus: &mut RawSensorBus) -> ActuatorBus {
         log::trace!("Running GNC FSW Loop");
 
-        // overwrite previous
+        // overwrite previous; rate groups not due this cycle hold their outputs
         std::mem::swap(&mut self.curr_state, &mut self.prev_state);
+        self.curr_state.clone_from(&self.prev_state);
+
+        // advance FSW clock
+        self.curr_state
+            .time_bus
+            .advance(fsw_time, &self.prev_state.time_bus, &self.param_bus);
 
         // read sensors
         self.curr_state.raw_sensor_bus = std::mem::take(raw_sensor_bus);
-        log::error!("{:?}", self.curr_state.raw_sensor_bus);
-
-        self.curr_state.tlm_sensor_bus.process(
-            // Current State
-            &self.curr_state.raw_sensor_bus,
-            // Previous State
-            &self.prev_state.estimation_bus,
-            // Params
-            &self.param_bus,
-        );
-        log::error!("{:?}", self.curr_state.tlm_sensor_bus);
-
-        // estimate state
-        self.curr_state.estimation_bus.process(
-            // Current State
-            &self.curr_state.tlm_sensor_bus,
-            // Previous State
-            &self.prev_state.estimation_bus,
-        );
-
-        // compute reference
-        self.curr_state.reference_bus.process(
-            // Current State
-            &self.curr_state.estimation_bus,
-            // Previous State
-            &self.prev_state.reference_bus,
-        );
-
-        // compute control error and commands
-        self.curr_state.control_bus.process(
-            // Current State
-            &self.curr_state.estimation_bus,
-            &self.curr_state.reference_bus,
-            // Previous State
-        );
-
-        // compute actuator commands
-        self.curr_state.actuator_bus.process(
-            // Current State
-            &self.curr_state.control_bus, // Previous State
-        );
+        log::trace!("{:?}", self.curr_state.raw_sensor_bus);
+
+        // run due rate groups
+        self.scheduler
+            .run(&mut self.curr_state, &self.prev_state, &self.param_bus);
+        log::trace!("{:?}", self.curr_state.tlm_sensor_bus);
 
         self.curr_state.actuator_bus.clone()
     }
 
+    // Per-sensor health, enable state and missed message counts
+    pub fn sensor_health_tlm(&self) -> Vec<SensorHealthTlm> {
+        self.curr_state.tlm_sensor_bus.health_tlm()
+    }
+
+    // Rate group timing / overruns
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    // Ground upload; applies from the next cycle
+    pub fn upload_eop(&mut self, eop: EarthOrientation) {
+        log::info!("EOP uploaded: {:?}", eop);
+        self.param_bus.eop = eop;
+    }
+
+    // Ground upload; replaces the full table
+    pub fn upload_leap_seconds(&mut self, table: &[(f64, f64)]) {
+        log::info!("Leap second table uploaded; {} entries", table.len());
+        self.param_bus.time.leap_seconds = LeapSeconds::new(table);
+    }
+
     // fn read_sensors(&mut self, raw_sensor_bus: Generic1D) {}
 }
@@ -0,0 +1,26 @@
+use crate::reference::types::ValidReference;
+use altai_rs::meta::types::Generic1D;
+
+#[derive(Clone, Debug)]
+pub struct Idle {
+    // ECI
+    q_hold_eci: Generic1D,
+}
+
+impl Idle {
+    pub fn new(q_hold_eci: Generic1D) -> Self {
+        Self { q_hold_eci }
+    }
+}
+
+impl ValidReference for Idle {
+    fn alpha_ref(&self) -> (Generic1D, bool) {
+        (Generic1D::zeros(3), false)
+    }
+    fn omega_ref(&self) -> (Generic1D, bool) {
+        (Generic1D::zeros(3), false)
+    }
+    fn q_ref_eci(&self) -> (Generic1D, bool) {
+        (self.q_hold_eci.to_owned(), false)
+    }
+}
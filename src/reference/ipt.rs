@@ -2,6 +2,7 @@ use crate::reference::types::ValidReference;
 use altai_rs::{meta::types::Generic1D, quatlib::dcm2quat, veclib::mfcross};
 use ndarray::{array, concatenate, s, Axis};
 
+#[derive(Clone, Debug)]
 pub struct InertialPointTrack {
     // SV
     pointing_axis: Generic1D,
@@ -16,7 +17,7 @@ pub struct InertialPointTrack {
 }
 
 impl InertialPointTrack {
-    fn new(
+    pub fn new(
         pointing_axis: Generic1D,
         power_axis: Generic1D,
         right_ascension: f64,
@@ -34,6 +35,15 @@ impl InertialPointTrack {
             u_sun,
         }
     }
+
+    // Fill in an ECI sun vector if neither roll nor sun was commanded
+    pub fn with_sun_fallback(&self, u_sun_eci: Option<Generic1D>) -> Self {
+        let mut ipt = self.clone();
+        if ipt.roll.is_none() && ipt.u_sun.is_none() {
+            ipt.u_sun = u_sun_eci;
+        }
+        ipt
+    }
 }
 
 impl ValidReference for InertialPointTrack {
@@ -54,16 +64,18 @@ impl ValidReference for InertialPointTrack {
             self.declination.sin()
         ];
 
-        if self.roll.is_some() {
+        if let Some(roll) = self.roll {
             // Align Rotation to Roll
-            let roll = self.roll.unwrap();
             let qvec = z_eci.to_owned() * (roll / 2.).sin();
             qvec.assign_to(q_ref_eci.slice_mut(s![0..3]));
             q_ref_eci[3] = (roll / 2.).cos();
         } else {
             // Align Power to Sun
-            let x_sv = mfcross(&self.power_axis, &z_sv);
-            let x_eci = mfcross(self.u_sun.as_ref().unwrap(), &z_eci);
+            let Some(u_sun) = self.u_sun.as_ref() else {
+                return (q_ref_eci, true);
+            };
+            let x_sv = mfcross(&self.power_axis, z_sv);
+            let x_eci = mfcross(u_sun, &z_eci);
 
             let t_sv = concatenate![
                 Axis(1),
@@ -77,12 +89,7 @@ impl ValidReference for InertialPointTrack {
             let t_eci = concatenate![
                 Axis(1),
                 x_eci.into_shape_with_order((3, 1)).unwrap(),
-                self.u_sun
-                    .as_ref()
-                    .unwrap()
-                    .to_owned()
-                    .into_shape_with_order((3, 1))
-                    .unwrap(),
+                u_sun.to_owned().into_shape_with_order((3, 1)).unwrap(),
                 z_eci.to_owned().into_shape_with_order((3, 1)).unwrap()
             ];
 
@@ -1,5 +1,6 @@
 pub mod types;
 
 // Reference Modes
+pub mod idle;
 pub mod ipt;
 pub mod slew;
@@ -0,0 +1,146 @@
+use crate::fsw_math::{qerror, qmult, qnormalize, quat2rotvec, rotvec2quat};
+use crate::reference::types::ValidReference;
+use altai_rs::meta::types::Generic1D;
+
+#[derive(Clone, Debug)]
+pub struct Slew {
+    // ECI
+    q_start_eci: Generic1D,
+    q_target_eci: Generic1D,
+
+    // SV; eigenaxis is common to start and target frames
+    eigenaxis: Generic1D,
+    angle: f64,
+
+    // Bang-Coast-Bang Profile
+    alpha_max: f64,
+    omega_peak: f64,
+    t_accel: f64,
+    t_coast: f64,
+    t_elapsed: f64,
+    profile_err: bool,
+}
+
+impl Slew {
+    pub fn new(
+        q_start_eci: Generic1D,
+        q_target_eci: Generic1D,
+        omega_max: f64,
+        alpha_max: f64,
+    ) -> Self {
+        let q_start_eci = qnormalize(&q_start_eci);
+        let q_target_eci = qnormalize(&q_target_eci);
+
+        // Shortest path; dq has positive scalar so angle <= pi
+        let rotvec = quat2rotvec(&qerror(&q_target_eci, &q_start_eci));
+        let angle = rotvec.dot(&rotvec).sqrt();
+        let eigenaxis = if angle > 0. {
+            rotvec / angle
+        } else {
+            Generic1D::zeros(3)
+        };
+
+        let profile_err = !(omega_max > 0. && alpha_max > 0.);
+        let (omega_peak, t_accel, t_coast) = if profile_err || angle == 0. {
+            (0., 0., 0.)
+        } else if angle >= omega_max.powi(2) / alpha_max {
+            // Trapezoidal: reaches max rate and coasts
+            let t_accel = omega_max / alpha_max;
+            (omega_max, t_accel, angle / omega_max - t_accel)
+        } else {
+            // Triangular: never reaches max rate
+            let t_accel = (angle / alpha_max).sqrt();
+            (alpha_max * t_accel, t_accel, 0.)
+        };
+
+        if profile_err {
+            log::error!(
+                "Invalid slew limits; omega_max: {} alpha_max: {}",
+                omega_max,
+                alpha_max
+            );
+        }
+
+        Self {
+            q_start_eci,
+            q_target_eci,
+            eigenaxis,
+            angle,
+            alpha_max,
+            omega_peak,
+            t_accel,
+            t_coast,
+            t_elapsed: 0.,
+            profile_err,
+        }
+    }
+
+    pub fn step(&mut self, dt: f64) {
+        self.t_elapsed = (self.t_elapsed + dt).min(self.duration());
+    }
+
+    pub fn duration(&self) -> f64 {
+        2. * self.t_accel + self.t_coast
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.profile_err || self.t_elapsed >= self.duration()
+    }
+
+    pub fn q_target_eci(&self) -> &Generic1D {
+        &self.q_target_eci
+    }
+
+    // (angle, rate, accel) about the eigenaxis at the current time
+    fn profile(&self) -> (f64, f64, f64) {
+        let t = self.t_elapsed;
+        let t_decel = self.t_accel + self.t_coast;
+        let angle_accel = 0.5 * self.alpha_max * self.t_accel.powi(2);
+
+        if self.profile_err || t >= self.duration() {
+            (self.angle, 0., 0.)
+        } else if t < self.t_accel {
+            (
+                0.5 * self.alpha_max * t.powi(2),
+                self.alpha_max * t,
+                self.alpha_max,
+            )
+        } else if t < t_decel {
+            (
+                angle_accel + self.omega_peak * (t - self.t_accel),
+                self.omega_peak,
+                0.,
+            )
+        } else {
+            let t_remain = self.duration() - t;
+            (
+                self.angle - 0.5 * self.alpha_max * t_remain.powi(2),
+                self.alpha_max * t_remain,
+                -self.alpha_max,
+            )
+        }
+    }
+}
+
+impl ValidReference for Slew {
+    fn alpha_ref(&self) -> (Generic1D, bool) {
+        let (_, _, accel) = self.profile();
+        (&self.eigenaxis * accel, self.profile_err)
+    }
+    fn omega_ref(&self) -> (Generic1D, bool) {
+        let (_, rate, _) = self.profile();
+        (&self.eigenaxis * rate, self.profile_err)
+    }
+    fn q_ref_eci(&self) -> (Generic1D, bool) {
+        if self.is_complete() {
+            return (self.q_target_eci.to_owned(), self.profile_err);
+        }
+
+        let (angle, _, _) = self.profile();
+        let q_ref_eci = qnormalize(&qmult(
+            &rotvec2quat(&(&self.eigenaxis * angle)),
+            &self.q_start_eci,
+        ));
+        (q_ref_eci, self.profile_err)
+    }
+}
@@ -1,17 +1,145 @@
 use altai_rs::meta::types::Generic1D;
+use ndarray::array;
 
 use crate::estimation::types::EstimationBus;
+use crate::fsw_types::ParamBus;
+use crate::reference::{idle::Idle, ipt::InertialPointTrack, slew::Slew};
+use crate::scheduler::{Phase, RateGroup, Scheduler};
+use crate::time::TimeBus;
 
-#[derive(Clone, Debug, Default)]
-pub struct ReferenceBus {}
+#[derive(Clone, Debug)]
+pub struct ReferenceBus {
+    pub mode: Reference,
+    pub q_ref_eci: Generic1D, // Commanded attitude wrt ECI
+    pub omega_ref: Generic1D, // Commanded rate in SC frame [rad/s]
+    pub alpha_ref: Generic1D, // Commanded accel in SC frame [rad/s^2]
+    pub ref_err: bool,
+
+    // Active reference generators
+    slew: Option<Slew>,
+    ipt: Option<InertialPointTrack>,
+}
 
 impl ReferenceBus {
-    pub fn process(_curr_est: &EstimationBus, _prev_ref: &ReferenceBus) -> Self {
-        Self {}
+    pub fn process(
+        &mut self,
+        curr_est: &EstimationBus,
+        time_bus: &TimeBus,
+        prev_ref: &ReferenceBus,
+        param_bus: &ParamBus,
+    ) {
+        // Carry mode + commands forward
+        self.clone_from(prev_ref);
+        let arch = &param_bus.acs_reference;
+
+        // Estimated sun vector for IPT w/o a commanded sun direction
+        let u_sun_eci = curr_est.sun_valid.then(|| curr_est.u_sun_eci.to_owned());
+
+        // Advance slew; start from current attitude if newly commanded
+        if self.mode == Reference::SLEW {
+            match (self.slew.as_mut(), self.ipt.as_ref()) {
+                (Some(slew), _) => slew.step(time_bus.dt()),
+                (None, Some(ipt)) => {
+                    let (q_target_eci, target_err) =
+                        ipt.with_sun_fallback(u_sun_eci.clone()).q_ref_eci();
+                    if target_err {
+                        // Hold and retry next cycle (e.g. sun not yet visible)
+                        log::warn!("IPT target unavailable; deferring slew");
+                    } else {
+                        if !curr_est.att_valid {
+                            log::warn!("Starting slew without a valid attitude estimate");
+                        }
+                        self.slew = Some(Slew::new(
+                            curr_est.q_sc_eci.to_owned(),
+                            q_target_eci,
+                            arch.slew_rate_max,
+                            arch.slew_accel_max,
+                        ));
+                    }
+                }
+                (None, None) => {
+                    log::error!("SLEW commanded without a follow-on target; reverting to IDLE");
+                    self.mode = Reference::IDLE;
+                }
+            }
+
+            // SLEW -> IPT
+            if self.slew.as_ref().is_some_and(|slew| slew.is_complete()) {
+                log::info!("Slew complete; transitioning to IPT");
+                self.mode = Reference::IPT;
+                self.slew = None;
+            }
+        }
+
+        // Dispatch
+        let (q_ref, o_ref, a_ref, err) = match (&self.mode, &self.slew, &self.ipt) {
+            (Reference::SLEW, Some(slew), _) => get_reference(slew),
+            (Reference::IPT, _, Some(ipt)) => get_reference(&ipt.with_sun_fallback(u_sun_eci)),
+            (Reference::IDLE, _, _) => get_reference(&Idle::new(curr_est.q_sc_eci.to_owned())),
+            _ => {
+                log::error!(
+                    "No reference generator for {:?}; holding attitude",
+                    self.mode
+                );
+                let (q_ref, o_ref, a_ref, _) =
+                    get_reference(&Idle::new(curr_est.q_sc_eci.to_owned()));
+                (q_ref, o_ref, a_ref, true)
+            }
+        };
+
+        self.q_ref_eci = q_ref;
+        self.omega_ref = o_ref;
+        self.alpha_ref = a_ref;
+        self.ref_err = err;
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        scheduler.register(
+            "REFERENCE",
+            RateGroup::FAST,
+            Phase::REFERENCE,
+            |curr, prev, time_bus, param_bus| {
+                curr.reference_bus.process(
+                    &curr.estimation_bus,
+                    time_bus,
+                    &prev.reference_bus,
+                    param_bus,
+                )
+            },
+        );
+    }
+
+    pub fn command_idle(&mut self) {
+        self.mode = Reference::IDLE;
+        self.slew = None;
+        self.ipt = None;
+    }
+
+    // Slews to the IPT target before tracking
+    pub fn command_ipt(&mut self, ipt: InertialPointTrack) {
+        self.mode = Reference::SLEW;
+        self.slew = None;
+        self.ipt = Some(ipt);
+    }
+}
+
+impl Default for ReferenceBus {
+    fn default() -> Self {
+        Self {
+            mode: Reference::IDLE,
+            q_ref_eci: array![0., 0., 0., 1.],
+            omega_ref: Generic1D::zeros(3),
+            alpha_ref: Generic1D::zeros(3),
+            ref_err: false,
+            slew: None,
+            ipt: None,
+        }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum Reference {
+    #[default]
     IDLE, // N/A
     SLEW, // MID-SLEW
     IPT,  // Inertial Point Track
@@ -23,7 +151,7 @@ pub trait ValidReference {
     fn alpha_ref(&self) -> (Generic1D, bool);
 }
 
-pub fn get_reference<T: ValidReference>(ref_cmd: T) -> (Generic1D, Generic1D, Generic1D, bool) {
+pub fn get_reference<T: ValidReference>(ref_cmd: &T) -> (Generic1D, Generic1D, Generic1D, bool) {
     let (q_ref, q_err) = ref_cmd.q_ref_eci();
     let (o_ref, o_err) = ref_cmd.omega_ref();
     let (a_ref, a_err) = ref_cmd.alpha_ref();
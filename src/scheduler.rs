@@ -0,0 +1,253 @@
+use std::time::Instant;
+
+use crate::fsw_types::{GNCState, Param, ParamBus};
+use crate::time::TimeBus;
+
+// Rate groups run off the GNC cycle; periods/budgets in SchedulerArchitecture
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RateGroup {
+    FAST,         // IMU, attitude, reference, control, actuators
+    STA,          // Star trackers
+    NAV,          // GPSR, clock correlation, orbit
+    HOUSEKEEPING, // Health summaries
+}
+impl RateGroup {
+    pub const ALL: [RateGroup; 4] = [
+        RateGroup::FAST,
+        RateGroup::STA,
+        RateGroup::NAV,
+        RateGroup::HOUSEKEEPING,
+    ];
+}
+
+// Execution order within a cycle; tasks run by phase, then registration order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    SENSORS,
+    TIME,
+    ESTIMATION,
+    REFERENCE,
+    CONTROL,
+    ACTUATORS,
+    HOUSEKEEPING,
+}
+
+// curr (carried forward from prev), prev, group time (dt over the group period), params
+pub type TaskFn = fn(&mut GNCState, &GNCState, &TimeBus, &ParamBus);
+
+#[derive(Clone, Debug)]
+struct Task {
+    name: &'static str,
+    group: RateGroup,
+    phase: Phase,
+    run: TaskFn,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RateGroupConfig {
+    pub period: f64, // Nominal period [s]
+    pub budget: f64, // Max execution time per run [s]
+}
+impl RateGroupConfig {
+    pub fn new(period: f64, budget: f64) -> Self {
+        Self { period, budget }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SchedulerArchitecture {
+    pub fast: RateGroupConfig,
+    pub sta: RateGroupConfig,
+    pub nav: RateGroupConfig,
+    pub housekeeping: RateGroupConfig,
+}
+impl SchedulerArchitecture {
+    // FAST runs every GNC cycle
+    pub fn initialize(gnc_dt: f64) -> Self {
+        Self {
+            fast: RateGroupConfig::new(gnc_dt, 0.5 * gnc_dt),
+            sta: RateGroupConfig::new(0.25, 0.02),
+            nav: RateGroupConfig::new(1., 0.05),
+            housekeeping: RateGroupConfig::new(10., 0.01),
+        }
+    }
+
+    pub fn group(&self, group: RateGroup) -> &RateGroupConfig {
+        match group {
+            RateGroup::FAST => &self.fast,
+            RateGroup::STA => &self.sta,
+            RateGroup::NAV => &self.nav,
+            RateGroup::HOUSEKEEPING => &self.housekeeping,
+        }
+    }
+}
+impl Default for SchedulerArchitecture {
+    fn default() -> Self {
+        Self::initialize(0.1)
+    }
+}
+impl Param for SchedulerArchitecture {}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RateGroupStatus {
+    active: bool,          // Ran this cycle
+    next_due: f64,         // FSW time of next run [s]
+    last_run: Option<f64>, // FSW time of last run [s]
+    dt: f64,               // Time since previous run [s]
+    exec_time: f64,        // Execution time of last run [s]
+    exec_max: f64,         // Worst execution time [s]
+    runs: u64,
+    overruns: u32, // Runs over execution budget
+    slips: u32,    // Due runs missed (skipped cycles)
+}
+impl RateGroupStatus {
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    pub fn exec_time(&self) -> f64 {
+        self.exec_time
+    }
+
+    pub fn exec_max(&self) -> f64 {
+        self.exec_max
+    }
+
+    pub fn runs(&self) -> u64 {
+        self.runs
+    }
+
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+
+    pub fn slips(&self) -> u32 {
+        self.slips
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Scheduler {
+    tasks: Vec<Task>,
+    status: [RateGroupStatus; 4],
+    cycle_exec: f64,     // Execution time of all groups last cycle [s]
+    cycle_overruns: u32, // Cycles exceeding the GNC period
+}
+
+impl Scheduler {
+    pub fn register(&mut self, name: &'static str, group: RateGroup, phase: Phase, run: TaskFn) {
+        log::trace!("Registering {} in {:?} / {:?}", name, group, phase);
+        self.tasks.push(Task {
+            name,
+            group,
+            phase,
+            run,
+        });
+
+        // Stable; registration order holds within a phase
+        self.tasks.sort_by_key(|task| task.phase);
+    }
+
+    // Run due rate groups; curr already carries prev forward
+    pub fn run(&mut self, curr: &mut GNCState, prev: &GNCState, param_bus: &ParamBus) {
+        let arch = &param_bus.scheduler;
+        let fsw_time = curr.time_bus.fsw_time();
+
+        // Round due times to the nearest cycle
+        let tol = 0.5 * param_bus.gnc_dt;
+        for group in RateGroup::ALL {
+            let period = arch.group(group).period;
+            let status = &mut self.status[group as usize];
+            if curr.time_bus.clock_jump() {
+                // Rephase on the new clock; time since last run carries across the jump
+                status.next_due = fsw_time;
+                let jump = curr.time_bus.jump_offset() - prev.time_bus.jump_offset();
+                status.last_run = status.last_run.map(|last_run| last_run + jump);
+            }
+
+            status.active = status.runs == 0 || fsw_time >= status.next_due - tol;
+            if !status.active {
+                continue;
+            }
+
+            status.dt = status
+                .last_run
+                .map_or(curr.time_bus.dt(), |last_run| fsw_time - last_run);
+            status.last_run = Some(fsw_time);
+            status.exec_time = 0.;
+
+            // Hold phase; skip rather than burst through missed runs
+            status.next_due = if status.runs == 0 {
+                fsw_time
+            } else {
+                status.next_due
+            } + period;
+            if status.next_due < fsw_time + tol {
+                let missed = ((fsw_time + tol - status.next_due) / period).floor() as u32 + 1;
+                log::warn!("{:?} rate group missed {} runs", group, missed);
+                status.slips = status.slips.saturating_add(missed);
+                status.next_due = fsw_time + period;
+            }
+        }
+
+        // Deterministic order: phase, then registration
+        for task in &self.tasks {
+            let status = &mut self.status[task.group as usize];
+            if !status.active {
+                continue;
+            }
+            let time_bus = curr.time_bus.with_dt(status.dt);
+            let start = Instant::now();
+            (task.run)(curr, prev, &time_bus, param_bus);
+            let exec_time = start.elapsed().as_secs_f64();
+            log::trace!("{} ran in {:.6} s", task.name, exec_time);
+            status.exec_time += exec_time;
+        }
+
+        // Execution time / overruns
+        self.cycle_exec = 0.;
+        for group in RateGroup::ALL {
+            let budget = arch.group(group).budget;
+            let status = &mut self.status[group as usize];
+            if !status.active {
+                continue;
+            }
+            status.runs += 1;
+            status.exec_max = status.exec_max.max(status.exec_time);
+            if status.exec_time > budget {
+                status.overruns = status.overruns.saturating_add(1);
+                log::warn!(
+                    "{:?} rate group overrun; {:.6} s vs budget {:.6} s",
+                    group,
+                    status.exec_time,
+                    budget
+                );
+            }
+            self.cycle_exec += status.exec_time;
+        }
+        if self.cycle_exec > param_bus.gnc_dt {
+            self.cycle_overruns = self.cycle_overruns.saturating_add(1);
+            log::error!(
+                "GNC cycle overrun; {:.6} s vs period {:.6} s",
+                self.cycle_exec,
+                param_bus.gnc_dt
+            );
+        }
+    }
+
+    pub fn status(&self, group: RateGroup) -> &RateGroupStatus {
+        &self.status[group as usize]
+    }
+
+    pub fn cycle_exec(&self) -> f64 {
+        self.cycle_exec
+    }
+
+    pub fn cycle_overruns(&self) -> u32 {
+        self.cycle_overruns
+    }
+}
@@ -0,0 +1,195 @@
+use crate::estimation::types::EstimationBus;
+use crate::fsw_math::inv;
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
+use altai_rs::types::*;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RawCSSPacket {
+    // Timestamped current coming directly from a single CSS photodiode
+    // Meta
+    raw_timestamp: u32,
+    raw_valid: bool,
+    msg_counter: u32,
+
+    // Sensor Specific
+    raw_current: f64, // Photodiode current [A]
+}
+impl RawSensorPacket for RawCSSPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
+}
+
+impl RawCSSPacket {
+    pub fn plant_update(
+        &mut self,
+        timestamp: u32,
+        raw_valid: bool,
+        inc_msg: bool,
+        raw_current: f64,
+    ) {
+        self.raw_timestamp = timestamp;
+        self.raw_valid = raw_valid;
+        self.msg_counter += inc_msg as u32;
+        self.raw_current = raw_current;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SensProcCSSBus {
+    // Processed data coming off CSS
+    // Meta
+    meta: SensorMeta,
+    n_css: usize,
+
+    // Sensor Specific
+    cos_sun: Generic1D,  // Normalized (cosine) response per photodiode
+    u_sun_sc: Generic1D, // Sun unit vector in SC frame
+    n_lit: usize,        // Photodiodes above albedo/noise threshold
+    sun_visible: bool,   // Sun vector solution available
+    eclipse: bool,       // No photodiode lit
+}
+
+impl Sensor for SensProcCSSBus {
+    type Packet = RawCSSPacket;
+    const NAME: &'static str = "CSS";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.css_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.css_fdir
+    }
+
+    fn check(
+        &mut self,
+        _packets: &[Self::Packet],
+        _time_bus: &TimeBus,
+        _prev_estimation_bus: &EstimationBus,
+        _param_bus: &ParamBus,
+    ) {
+        // Check over-range
+        let range_check = self
+            .cos_sun
+            .iter()
+            .zip(self.enabled())
+            .all(|(c, enabled)| !enabled || *c < 1.5);
+        self.update_hw_test(range_check, 4);
+    }
+
+    fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
+        let arch = &param_bus.acs_sensors;
+        if arch.css_scale.len() < self.n_css || arch.css_normals.ncols() < self.n_css {
+            log::error!("CSS architecture does not cover {} photodiodes", self.n_css);
+            self.sun_visible = false;
+            return;
+        }
+
+        // Normalize to cosine of incidence
+        self.cos_sun = Generic1D::from_shape_fn(self.n_css, |idx| {
+            packets[idx].raw_current / arch.css_scale[idx]
+        });
+
+        // Remove albedo / noise; only usable, lit photodiodes contribute
+        let lit: Vec<usize> = (0..self.n_css)
+            .filter(|&idx| self.usable()[idx] && self.cos_sun[idx] > arch.css_threshold)
+            .collect();
+        self.n_lit = lit.len();
+        self.eclipse = lit.is_empty();
+
+        // Least squares: N * s = c  =>  s = (N^T N)^-1 N^T c
+        let normals = Generic2D::from_shape_fn((self.n_lit, 3), |(row, col)| {
+            arch.css_normals[[col, lit[row]]]
+        });
+        let cos_lit = Generic1D::from_shape_fn(self.n_lit, |idx| self.cos_sun[lit[idx]]);
+        let solution = (self.n_lit >= 3)
+            .then(|| inv(&normals.t().dot(&normals)))
+            .flatten()
+            .map(|ntn_inv| ntn_inv.dot(&normals.t().dot(&cos_lit)))
+            .filter(|s_sun| s_sun.dot(s_sun) > 0.);
+
+        self.sun_visible = solution.is_some();
+        if let Some(s_sun) = solution {
+            self.u_sun_sc = &s_sun / s_sun.dot(&s_sun).sqrt();
+        }
+    }
+
+    fn hardware_subtest(&self) -> u16 {
+        /* MSB
+        15
+        14
+        13
+        12
+        11
+        10
+        09
+        08
+        07
+        06
+        05
+        04: All CSS normalized response < 1.5 (no over-range)
+        03: All CSS Timestamp within sync_tol of average
+        02: >=1 enabled CSS Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
+        LSB */
+        self.health().word()
+    }
+}
+
+impl SensProcCSSBus {
+    pub fn initialize(n_css: usize) -> Self {
+        Self {
+            // Meta
+            meta: SensorMeta::initialize(n_css),
+            n_css,
+
+            // Sensor-Specific
+            cos_sun: Generic1D::zeros(n_css),
+            u_sun_sc: Generic1D::zeros(3),
+            n_lit: 0,
+            sun_visible: false,
+            eclipse: false,
+        }
+    }
+
+    pub fn u_sun_sc(&self) -> &Generic1D {
+        &self.u_sun_sc
+    }
+
+    pub fn sun_visible(&self) -> bool {
+        self.sun_visible
+    }
+
+    pub fn eclipse(&self) -> bool {
+        self.eclipse
+    }
+}
+
+impl Default for SensProcCSSBus {
+    fn default() -> Self {
+        let n_css = 1;
+        Self::initialize(n_css)
+    }
+}
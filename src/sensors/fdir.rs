@@ -0,0 +1,110 @@
+// Persistence on a single health bit
+// Sets after set_n failures within the last set_m checks; clears after clear_n
+// consecutive passes unless latched
+#[derive(Clone, Copy, Debug)]
+pub struct BitPersistence {
+    pub set_n: u8,   // Failures to set
+    pub set_m: u8,   // Window of checks [1, 16]
+    pub clear_n: u8, // Consecutive passes to clear
+    pub latch: bool, // Hold once set until commanded clear
+}
+impl BitPersistence {
+    pub fn new(set_n: u8, set_m: u8, clear_n: u8, latch: bool) -> Self {
+        Self {
+            set_n,
+            set_m,
+            clear_n,
+            latch,
+        }
+    }
+}
+impl Default for BitPersistence {
+    // Instantaneous; no filtering
+    fn default() -> Self {
+        Self::new(1, 1, 1, false)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FdirConfig {
+    pub bits: [BitPersistence; 16],
+}
+impl FdirConfig {
+    pub fn with_bit(mut self, bit_id: u8, persistence: BitPersistence) -> Self {
+        self.bits[bit_id as usize] = persistence;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FdirMonitor {
+    config: FdirConfig,
+    status: u16,            // Persisted fault word (hardware subtest)
+    latched: u16,           // Faults held until commanded clear
+    history: [u16; 16],     // Per-bit shift register of recent failures (LSB newest)
+    pass_count: [u8; 16],   // Per-bit consecutive passes while set
+    fault_count: [u32; 16], // Per-bit number of times set
+}
+
+impl FdirMonitor {
+    // Config may change between cycles
+    pub fn configure(&mut self, config: &FdirConfig) {
+        self.config = *config;
+    }
+
+    // Record a check; flag = pass
+    pub fn update(&mut self, flag: bool, bit_id: u8) {
+        if bit_id > 15 {
+            panic!("Invalid bit setting for u16 bitpack")
+        }
+        let bit = bit_id as usize;
+        let mask = 1u16 << bit_id;
+        let cfg = self.config.bits[bit];
+
+        let window = (1u32 << cfg.set_m.clamp(1, 16)) - 1;
+        self.history[bit] = (((self.history[bit] as u32) << 1 | !flag as u32) & window) as u16;
+
+        if self.status & mask == 0 {
+            if self.history[bit].count_ones() >= cfg.set_n.max(1) as u32 {
+                self.status |= mask;
+                self.pass_count[bit] = 0;
+                self.fault_count[bit] = self.fault_count[bit].saturating_add(1);
+                if cfg.latch {
+                    self.latched |= mask;
+                }
+            }
+        } else {
+            self.pass_count[bit] = if flag {
+                self.pass_count[bit].saturating_add(1)
+            } else {
+                0
+            };
+            if self.latched & mask == 0 && self.pass_count[bit] >= cfg.clear_n.max(1) {
+                self.status &= !mask;
+                self.history[bit] = 0;
+            }
+        }
+    }
+
+    pub fn word(&self) -> u16 {
+        self.status
+    }
+
+    pub fn latched(&self) -> u16 {
+        self.latched
+    }
+
+    pub fn fault_count(&self) -> &[u32; 16] {
+        &self.fault_count
+    }
+
+    // Clear faults (incl. latched) for the masked bits; counters are kept
+    pub fn command_clear(&mut self, mask: u16) {
+        self.status &= !mask;
+        self.latched &= !mask;
+        for bit in (0..16).filter(|bit| mask & (1 << bit) != 0) {
+            self.history[bit] = 0;
+            self.pass_count[bit] = 0;
+        }
+    }
+}
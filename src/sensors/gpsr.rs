@@ -1,34 +1,75 @@
-use crate::{fsw_types::ParamBus, sensors::types::*};
+use crate::estimation::{orbit, types::EstimationBus};
+use crate::frames;
+use crate::time::{GpsTime, TimeBus};
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
 use altai_rs::types::*;
+use ndarray::s;
+
+// Per-receiver health flags
+pub const RX_INVALID: u8 = 1 << 0; // Disabled, invalid or stale counter
+pub const RX_SATS: u8 = 1 << 1; // Too few satellites
+pub const RX_AGE: u8 = 1 << 2; // Fix age outside window
+pub const RX_POS: u8 = 1 << 3; // POS outside tol of propagated est
+pub const RX_VEL: u8 = 1 << 4; // VEL outside tol of propagated est
+pub const RX_ALT: u8 = 1 << 5; // Altitude outside window
+pub const RX_VOTE: u8 = 1 << 6; // Outvoted by other receivers
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct RawGPSRPacket {
-    // Timestamped attitude coming direct from startracker
+    // Timestamped ECEF fix coming direct from GPS receiver
     // Meta
     raw_timestamp: u32,
     raw_valid: bool,
     msg_counter: u32,
 
     // Sensor-Specific Data
-    raw_r_eci: [f64; 3], // TODO: REPLACE w R_ECEF
-    raw_v_eci: [f64; 3], // TODO: REPLACE w V_ECEF
+    raw_r_ecef: [f64; 3], // SV Position in ECEF (ITRF) [m]
+    raw_v_ecef: [f64; 3], // SV Velocity in ECEF (ITRF) [m/s]
+    gps_week: u32,        // Fix epoch; weeks since 1980-01-06 (unrolled)
+    gps_seconds: f64,     // Fix epoch; seconds of week [s]
+    raw_fom: f64,         // Position figure of merit (1-sigma) [m]
+    n_sats: u8,           // Satellites used in fix
+}
+impl RawSensorPacket for RawGPSRPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
 }
-impl RawSensorPacket for RawGPSRPacket {}
 
 impl RawGPSRPacket {
+    #[allow(clippy::too_many_arguments)]
     pub fn plant_update(
         &mut self,
         timestamp: u32,
         raw_valid: bool,
         inc_msg: bool,
-        raw_r_eci: [f64; 3],
-        raw_v_eci: [f64; 3],
+        raw_r_ecef: [f64; 3],
+        raw_v_ecef: [f64; 3],
+        gps_week: u32,
+        gps_seconds: f64,
+        raw_fom: f64,
+        n_sats: u8,
     ) {
         self.raw_timestamp = timestamp;
         self.raw_valid = raw_valid;
         self.msg_counter += inc_msg as u32;
-        self.raw_r_eci = raw_r_eci;
-        self.raw_v_eci = raw_v_eci;
+        self.raw_r_ecef = raw_r_ecef;
+        self.raw_v_ecef = raw_v_ecef;
+        self.gps_week = gps_week;
+        self.gps_seconds = gps_seconds;
+        self.raw_fom = raw_fom;
+        self.n_sats = n_sats;
     }
 }
 
@@ -36,71 +77,210 @@ impl RawGPSRPacket {
 pub struct SensProcGPSRBus {
     // Processed data coming off STA
     // Meta
-    timestamp: u32,
-    error_code: u16,
+    meta: SensorMeta,
     n_gpsr: usize,
-    prev_msg_counter: u32,
 
     // Sensor-Specific
-    r_eci: Vector3, // SV Position in ECI // TODO: REPLACE w/ R_ECEF
-    v_eci: Vector3, // SV Velocity in ECI // TODO: REPLACE w/ R_ECEF
+    r_eci: Vector3, // SV Position in ECI, rotated from ECEF [m]
+    v_eci: Vector3, // SV Velocity in ECI, rotated from ECEF [m/s]
+
+    // Consistency FDIR
+    fix_consistent: bool,     // Selected fix passed pos/vel/alt + voting checks
+    fix_time: Vec<GpsTime>,   // Per-receiver fix epoch
+    selected: Option<usize>,  // Receiver providing the best fix
+    receiver_health: Vec<u8>, // Per-receiver failed checks (RX_*); 0 = healthy
+    gate_rejects: u32,        // Consecutive checks w/ every plausible fix off the estimate
+    orbit_reinit: bool,       // Estimate diverged; re-initialize orbit from the selected fix
 }
 
 impl Sensor for SensProcGPSRBus {
     type Packet = RawGPSRPacket;
-    fn process(
+    const NAME: &'static str = "GPSR";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.gpsr_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.gpsr_fdir
+    }
+
+    fn check(
         &mut self,
         packets: &[Self::Packet],
-        _prev_estimation_bus: &crate::estimation::types::EstimationBus,
+        time_bus: &TimeBus,
+        prev_estimation_bus: &EstimationBus,
         param_bus: &ParamBus,
     ) {
-        // Reset
-        self.error_code = 0u16;
-
-        // Check Enabled
-        let enabled = true; // TODO -> External Check
-        self.update_hw_test(enabled, 0); // HW Valid if Enabled
-
-        // Check Message Counter
-        let msg_inc = packets.iter().fold(true, |flag, gps| {
-            flag & (gps.msg_counter != self.prev_msg_counter)
-        });
-        self.prev_msg_counter = packets[0].msg_counter;
-        self.update_hw_test(msg_inc, 1); // HW Valid if MSG Counter Incrementing
-
-        // Check Raw Valid
-        let valid = packets
+        let arch = &param_bus.acs_sensors;
+        self.receiver_health = packets[..self.n_gpsr]
+            .iter()
+            .enumerate()
+            .map(|(col, gps)| {
+                let mut flags = 0u8;
+                flags |= (!self.enabled()[col] || !gps.raw_valid || self.msg_stale()[col]) as u8
+                    * RX_INVALID;
+                flags |= ((gps.n_sats as u32) < arch.gpsr_sats_min) as u8 * RX_SATS;
+                flags
+            })
+            .collect();
+
+        // Latency compensation; propagate each fix to cycle time
+        let clock = param_bus.acs_sensors.gpsr_clock;
+        for col in 0..self.n_gpsr {
+            if !clock.age_ok(self.age()[col]) {
+                self.receiver_health[col] |= RX_AGE;
+                continue;
+            }
+            let (r_now, v_now) = orbit::propagate_state(
+                &self.r_eci.column(col).to_owned(),
+                &self.v_eci.column(col).to_owned(),
+                self.age()[col],
+            );
+            self.r_eci.column_mut(col).assign(&r_now);
+            self.v_eci.column_mut(col).assign(&v_now);
+        }
+
+        // Consistency w/ propagated estimate; gate grows w/ estimate uncertainty
+        let prev = prev_estimation_bus;
+        let (r_prop, v_prop) = orbit::propagate_state(&prev.r_eci, &prev.v_eci, time_bus.dt());
+        let sigma = |block: usize| {
+            prev.cov_orbit
+                .slice(s![block..block + 3, block..block + 3])
+                .diag()
+                .sum()
+                .sqrt()
+        };
+        let pos_tol = arch.gpsr_pos_tol.max(3. * sigma(0));
+        let vel_tol = arch.gpsr_vel_tol.max(3. * sigma(3));
+        for col in 0..self.n_gpsr {
+            let r = self.r_eci.column(col);
+            let v = self.v_eci.column(col);
+            if prev.orbit_valid {
+                let dr = &r - &r_prop;
+                let dv = &v - &v_prop;
+                self.receiver_health[col] |= (dr.dot(&dr).sqrt() >= pos_tol) as u8 * RX_POS;
+                self.receiver_health[col] |= (dv.dot(&dv).sqrt() >= vel_tol) as u8 * RX_VEL;
+            }
+            let alt = r.dot(&r).sqrt() - orbit::R_EARTH;
+            let alt_ok = (arch.gpsr_alt_min..=arch.gpsr_alt_max).contains(&alt);
+            self.receiver_health[col] |= !alt_ok as u8 * RX_ALT;
+        }
+        let agrees = |a: usize, b: usize| {
+            let dr = &self.r_eci.column(a) - &self.r_eci.column(b);
+            let dv = &self.v_eci.column(a) - &self.v_eci.column(b);
+            dr.dot(&dr).sqrt() < arch.gpsr_pos_tol && dv.dot(&dv).sqrt() < arch.gpsr_vel_tol
+        };
+
+        // Recovery; estimate has diverged if every plausible fix fails only the estimate
+        // gate, persistently, while the receivers agree with each other
+        let gate = RX_POS | RX_VEL;
+        let plausible: Vec<usize> = (0..self.n_gpsr)
+            .filter(|col| self.receiver_health[*col] & !gate == 0)
+            .collect();
+        let diverged = !plausible.is_empty()
+            && plausible
+                .iter()
+                .all(|col| self.receiver_health[*col] & gate != 0)
+            && plausible
+                .iter()
+                .all(|&a| plausible.iter().all(|&b| a == b || agrees(a, b)));
+        self.gate_rejects = if diverged { self.gate_rejects + 1 } else { 0 };
+        self.orbit_reinit = self.gate_rejects >= arch.gpsr_reinit_n;
+        if self.orbit_reinit {
+            log::warn!(
+                "GPSR fixes off orbit estimate for {} checks; re-initializing orbit",
+                self.gate_rejects
+            );
+            for col in &plausible {
+                self.receiver_health[*col] &= !gate;
+            }
+            self.gate_rejects = 0;
+        }
+
+        // Cross-receiver voting; reject receivers outvoted by the agreeing majority
+        let candidates: Vec<usize> = (0..self.n_gpsr)
+            .filter(|col| self.receiver_health[*col] == 0)
+            .collect();
+        let votes: Vec<usize> = candidates
             .iter()
-            .fold(0, |acc, gps| acc + gps.raw_valid as usize)
-            > self.n_gpsr / 2;
-        self.update_hw_test(valid, 2); // Valid if >half STA is valid
-
-        // Check timestamp staleness
-        self.timestamp =
-            packets.iter().fold(0, |acc, gps| acc + gps.raw_timestamp) / self.n_gpsr as u32;
-        let valid = packets.iter().fold(true, |acc, gps| {
-            acc | ((self.timestamp as i32 - gps.raw_timestamp as i32).abs() < 10)
-        });
-        self.update_hw_test(valid, 3); // Valid if each timestamp within 1 sec of average
-
-        // Update Data
-        self.ingest(packets, param_bus);
-
-        // Check Pos Difference
-        let rcheck = true; // TODO: Update to compre pos w/ prev est bus
-        self.update_hw_test(rcheck, 4);
-    }
-
-    fn ingest(&mut self, packets: &[Self::Packet], _param_bus: &ParamBus) {
-        // Move to Self
-        self.r_eci
-            .assign(&Vector3::from_shape_fn((3, self.n_gpsr), |(row, col)| {
-                packets[row].raw_r_eci[col]
-            }));
-        self.v_eci
-            .assign(&Vector3::from_shape_fn((3, 1), |(row, col)| {
-                packets[row].raw_v_eci[col]
-            }));
+            .map(|&a| {
+                candidates
+                    .iter()
+                    .filter(|&&b| b != a && agrees(a, b))
+                    .count()
+            })
+            .collect();
+        let vote_ok = match candidates.len() {
+            0 | 1 => true,
+            2 => votes[0] > 0, // Disagreement detectable, not isolatable
+            _ => {
+                for (idx, &col) in candidates.iter().enumerate() {
+                    if 2 * votes[idx] < candidates.len() - 1 {
+                        log::warn!("GPSR {} outvoted by other receivers", col);
+                        self.receiver_health[col] |= RX_VOTE;
+                    }
+                }
+                candidates.iter().any(|col| self.receiver_health[*col] == 0)
+            }
+        };
+
+        // Best fix; lowest figure of merit, then most satellites
+        self.selected = (0..self.n_gpsr)
+            .filter(|col| self.receiver_health[*col] == 0)
+            .min_by(|&a, &b| {
+                packets[a]
+                    .raw_fom
+                    .total_cmp(&packets[b].raw_fom)
+                    .then(packets[b].n_sats.cmp(&packets[a].n_sats))
+            });
+        self.fix_consistent = self.selected.is_some() && vote_ok;
+
+        // Single bad fix is rejected but does not fail the receiver (see gpsr_fdir)
+        // Only receivers with a usable fix count toward a check passing
+        let any_ok = |mask: u8| {
+            self.receiver_health
+                .iter()
+                .any(|rx| rx & (RX_INVALID | RX_SATS | mask) == 0)
+        };
+        let (pos_ok, vel_ok, alt_ok) = (any_ok(RX_POS), any_ok(RX_VEL), any_ok(RX_ALT));
+        let age_ok = any_ok(RX_AGE);
+        self.update_hw_test(pos_ok, 4);
+        self.update_hw_test(vel_ok, 5);
+        self.update_hw_test(alt_ok, 6);
+        self.update_hw_test(age_ok, 7);
+        self.update_hw_test(vote_ok, 8);
+    }
+
+    fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
+        // Rotate to ECI at fix epoch
+        for (col, gps) in packets[..self.n_gpsr].iter().enumerate() {
+            self.fix_time[col] = GpsTime::new(gps.gps_week, gps.gps_seconds);
+            let (jd_tt, jd_ut1) = frames::gps2jd(
+                &self.fix_time[col],
+                &param_bus.time.leap_seconds,
+                &param_bus.eop,
+            );
+            let (r_eci, v_eci) = frames::ecef2eci(
+                &Generic1D::from(gps.raw_r_ecef.to_vec()),
+                &Generic1D::from(gps.raw_v_ecef.to_vec()),
+                jd_tt,
+                jd_ut1,
+                &param_bus.eop,
+            );
+
+            // Move to Self
+            self.r_eci.column_mut(col).assign(&r_eci);
+            self.v_eci.column_mut(col).assign(&v_eci);
+        }
     }
 
     fn hardware_subtest(&self) -> u16 {
@@ -112,37 +292,73 @@ impl Sensor for SensProcGPSRBus {
         11
         10
         09
-        08
-        07
-        06
-        05
-        04: GPS reported POS < 1km of prev est
-        03: All GPS Timestamp < 1 sec from average
-        02: >n/2 GPS Valid
-        01: MsgCounter Increasing
-        00: Enabled
+        08: Healthy receivers agree (2 receivers; >2 outvoted receivers are isolated)
+        07: >=1 GPS fix age within max
+        06: >=1 GPS reported altitude within window (persistent)
+        05: >=1 GPS reported VEL within tol of propagated est (persistent)
+        04: >=1 GPS reported POS within tol of propagated est (persistent)
+        03: All GPS Timestamp within sync_tol of average
+        02: >=1 enabled GPS Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
         LSB */
-        self.error_code
+        self.health().word()
     }
 }
 
 impl SensProcGPSRBus {
     pub fn initialize(n_gpsr: usize) -> Self {
         Self {
-            timestamp: 0,
+            // Meta
+            meta: SensorMeta::initialize(n_gpsr),
             n_gpsr,
-            error_code: 0u16,
-            prev_msg_counter: 0u32,
             r_eci: Vector3::zeros((3, n_gpsr)),
             v_eci: Vector3::zeros((3, n_gpsr)),
+            fix_consistent: false,
+            fix_time: vec![GpsTime::default(); n_gpsr],
+            selected: None,
+            receiver_health: vec![0; n_gpsr],
+            gate_rejects: 0,
+            orbit_reinit: false,
         }
     }
 
-    fn update_hw_test(&mut self, flag: bool, bit_id: u8) {
-        if bit_id > 15 {
-            panic!("Invalid bit setting for u16 bitpack")
-        }
-        self.error_code ^= (0b1 * !flag as u16) << bit_id;
+    pub fn r_eci(&self) -> &Vector3 {
+        &self.r_eci
+    }
+
+    pub fn v_eci(&self) -> &Vector3 {
+        &self.v_eci
+    }
+
+    pub fn fix_consistent(&self) -> bool {
+        self.fix_consistent
+    }
+
+    pub fn fix_time(&self, col: usize) -> GpsTime {
+        self.fix_time[col]
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn receiver_health(&self) -> &[u8] {
+        &self.receiver_health
+    }
+
+    pub fn orbit_reinit(&self) -> bool {
+        self.orbit_reinit
+    }
+
+    // Selected ECI position/velocity at cycle time
+    pub fn best_fix(&self) -> Option<(Generic1D, Generic1D)> {
+        self.selected.map(|col| {
+            (
+                self.r_eci.column(col).to_owned(),
+                self.v_eci.column(col).to_owned(),
+            )
+        })
     }
 }
 
@@ -1,5 +1,10 @@
 use crate::estimation::types::EstimationBus;
-use crate::{fsw_types::ParamBus, sensors::types::*};
+use crate::fsw_math::inv;
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
 use altai_rs::{quatlib::qxform, types::*};
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -14,7 +19,19 @@ pub struct RawIMUPacket {
     raw_gyro: [f64; 3],  // Rates in IMU frame
     raw_accel: [f64; 3], // Accel in IMU frame
 }
-impl RawSensorPacket for RawIMUPacket {}
+impl RawSensorPacket for RawIMUPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
+}
 
 impl RawIMUPacket {
     pub fn plant_update(
@@ -37,55 +54,57 @@ impl RawIMUPacket {
 pub struct SensProcIMUBus {
     // Processed data coming off IMU
     // Meta
-    timestamp: u32,
-    error_code: u16,
+    meta: SensorMeta,
     n_imu: usize,
-    prev_msg_counter: u32,
 
     // Sensor Specific
     gyro_sc: Vector3,  // Rates in SC frame
     accel_sc: Vector3, // Accel in SC frame
+
+    // Fused (FDIR)
+    gyro_fused: Generic1D,      // Fused rate in SC frame [rad/s]
+    gyro_cov: Generic2D,        // Fused rate covariance [(rad/s)^2]
+    accel_fused: Generic1D,     // Fused accel in SC frame [m/s^2]
+    accel_cov: Generic2D,       // Fused accel covariance [(m/s^2)^2]
+    gyro_isolated: Vec<bool>,   // Latched per-axis gyro isolation (3 per head)
+    accel_isolated: Vec<bool>,  // Latched per-axis accel isolation (3 per head)
+    head_isolated: Vec<bool>,   // Latched per-head isolation (>=2 axes isolated)
+    gyro_fault_detected: bool,  // Unisolated gyro inconsistency
+    accel_fault_detected: bool, // Unisolated accel inconsistency
 }
 
 impl Sensor for SensProcIMUBus {
     type Packet = RawIMUPacket;
-    fn process(
+    const NAME: &'static str = "IMU";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.imu_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.imu_fdir
+    }
+
+    fn check(
         &mut self,
         packets: &[Self::Packet],
+        _time_bus: &TimeBus,
         _prev_estimation_bus: &EstimationBus,
         param_bus: &ParamBus,
     ) {
-        // Reset
-        self.error_code = 0u16;
-
-        // Check Enabled
-        let enabled = true; // TODO -> External Check
-        self.update_hw_test(enabled, 0); // HW Valid if Enabled
-
-        // Check Message Counter
-        let msg_inc = packets.iter().fold(true, |flag, imu| {
-            flag & (imu.msg_counter != self.prev_msg_counter)
-        });
-        self.prev_msg_counter = packets[0].msg_counter;
-        self.update_hw_test(msg_inc, 1); // HW Valid if MSG Counter Incrementing
-
-        // Check Raw Valid
-        let valid = packets
-            .iter()
-            .fold(0, |acc, imu| acc + imu.raw_valid as usize)
-            > self.n_imu / 2;
-        self.update_hw_test(valid, 2); // Valid if >half IMU is valid
-
-        // Check timestamp staleness
-        self.timestamp =
-            packets.iter().fold(0, |acc, imu| acc + imu.raw_timestamp) / self.n_imu as u32;
-        let valid = packets.iter().fold(true, |acc, imu| {
-            acc | ((self.timestamp as i32 - imu.raw_timestamp as i32).abs() < 10)
-        });
-        self.update_hw_test(valid, 3); // Valid if each timestamp within 1 sec of average
-
-        // Update Data
-        self.ingest(packets, param_bus);
+        // Fuse redundant heads; isolate failed axes/heads
+        let gyro_ok = self.fuse_gyro(packets, param_bus);
+        let accel_ok = self.fuse_accel(packets, param_bus);
+        self.update_hw_test(gyro_ok && !self.gyro_fault_detected, 4);
+        self.update_hw_test(accel_ok && !self.accel_fault_detected, 5);
     }
 
     fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
@@ -117,14 +136,14 @@ impl Sensor for SensProcIMUBus {
         08
         07
         06
-        05
-        04
-        03: All IMU Timestamp < 1 sec from average
-        02: >n/2 IMU Valid
-        01: MsgCounter Increasing
-        00: Enabled
+        05: Fused accel observable and consistent
+        04: Fused gyro observable and consistent
+        03: All IMU Timestamp within sync_tol of average
+        02: >=1 enabled IMU Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
         LSB */
-        self.error_code
+        self.health().word()
     }
 }
 
@@ -132,22 +151,253 @@ impl SensProcIMUBus {
     pub fn initialize(n_imu: usize) -> Self {
         Self {
             // Meta
-            timestamp: 0,
-            error_code: 0u16,
+            meta: SensorMeta::initialize(n_imu),
             n_imu,
-            prev_msg_counter: 0u32,
 
             // Sensor-Specific
             gyro_sc: Vector3::zeros((3, n_imu)),
             accel_sc: Vector3::zeros((3, n_imu)),
+
+            // Fused
+            gyro_fused: Generic1D::zeros(3),
+            gyro_cov: Generic2D::zeros((3, 3)),
+            accel_fused: Generic1D::zeros(3),
+            accel_cov: Generic2D::zeros((3, 3)),
+            gyro_isolated: vec![false; 3 * n_imu],
+            accel_isolated: vec![false; 3 * n_imu],
+            head_isolated: vec![false; n_imu],
+            gyro_fault_detected: false,
+            accel_fault_detected: false,
         }
     }
 
-    fn update_hw_test(&mut self, flag: bool, bit_id: u8) {
-        if bit_id > 15 {
-            panic!("Invalid bit setting for u16 bitpack")
+    pub fn gyro_fused(&self) -> &Generic1D {
+        &self.gyro_fused
+    }
+
+    pub fn gyro_cov(&self) -> &Generic2D {
+        &self.gyro_cov
+    }
+
+    pub fn accel_fused(&self) -> &Generic1D {
+        &self.accel_fused
+    }
+
+    pub fn accel_cov(&self) -> &Generic2D {
+        &self.accel_cov
+    }
+
+    pub fn gyro_isolated(&self) -> &[bool] {
+        &self.gyro_isolated
+    }
+
+    pub fn accel_isolated(&self) -> &[bool] {
+        &self.accel_isolated
+    }
+
+    pub fn head_isolated(&self) -> &[bool] {
+        &self.head_isolated
+    }
+
+    // Restore axes to service w/ their fault bits; bit 4 gyro, bit 5 accel
+    pub fn clear_isolation(&mut self, mask: u16) {
+        if mask & (1 << 4) != 0 {
+            self.gyro_isolated.fill(false);
+        }
+        if mask & (1 << 5) != 0 {
+            self.accel_isolated.fill(false);
+        }
+
+        // Heads stay isolated while >=2 of either sensor's axes still are
+        let n_iso = |isolated: &[bool], head: usize| {
+            isolated[3 * head..3 * head + 3]
+                .iter()
+                .filter(|iso| **iso)
+                .count()
+        };
+        for head in 0..self.n_imu {
+            self.head_isolated[head] =
+                n_iso(&self.gyro_isolated, head) >= 2 || n_iso(&self.accel_isolated, head) >= 2;
         }
-        self.error_code ^= (0b1 * !flag as u16) << bit_id;
+    }
+
+    fn fuse_gyro(&mut self, packets: &[RawIMUPacket], param_bus: &ParamBus) -> bool {
+        let arch = &param_bus.acs_sensors;
+        let raw: Vec<[f64; 3]> = packets.iter().map(|imu| imu.raw_gyro).collect();
+        let (meas, h) = self.measurement_model(&raw, &self.gyro_sc, &arch.imu_axes);
+        let mut active = self.active_axes(packets, &self.gyro_isolated);
+
+        let result = fuse_fdi(&meas, &h, &mut active, arch.imu_gyro_sigma, arch.imu_fdi_k);
+        self.latch_isolation(packets, &active, true);
+        match result {
+            Some((fused, cov, fault)) => {
+                self.gyro_fused = fused;
+                self.gyro_cov = cov;
+                self.gyro_fault_detected = fault;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn fuse_accel(&mut self, packets: &[RawIMUPacket], param_bus: &ParamBus) -> bool {
+        let arch = &param_bus.acs_sensors;
+        let raw: Vec<[f64; 3]> = packets.iter().map(|imu| imu.raw_accel).collect();
+        let (meas, h) = self.measurement_model(&raw, &self.accel_sc, &arch.imu_axes);
+        let mut active = self.active_axes(packets, &self.accel_isolated);
+
+        let result = fuse_fdi(&meas, &h, &mut active, arch.imu_accel_sigma, arch.imu_fdi_k);
+        self.latch_isolation(packets, &active, false);
+        match result {
+            Some((fused, cov, fault)) => {
+                self.accel_fused = fused;
+                self.accel_cov = cov;
+                self.accel_fault_detected = fault;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Per-axis measurements w/ SC-frame sense axes; raw axes if skewed config is given
+    fn measurement_model(
+        &self,
+        raw: &[[f64; 3]],
+        data_sc: &Vector3,
+        imu_axes: &Generic2D,
+    ) -> (Generic1D, Generic2D) {
+        let n_axes = 3 * self.n_imu;
+        if imu_axes.ncols() == n_axes {
+            (
+                Generic1D::from_shape_fn(n_axes, |idx| raw[idx / 3][idx % 3]),
+                imu_axes.t().to_owned(),
+            )
+        } else {
+            (
+                Generic1D::from_shape_fn(n_axes, |idx| data_sc[[idx % 3, idx / 3]]),
+                Generic2D::from_shape_fn((n_axes, 3), |(row, col)| (row % 3 == col) as u8 as f64),
+            )
+        }
+    }
+
+    fn active_axes(&self, packets: &[RawIMUPacket], isolated: &[bool]) -> Vec<bool> {
+        (0..3 * self.n_imu)
+            .map(|idx| {
+                let head = idx / 3;
+                self.head_usable(packets, head) && !isolated[idx]
+            })
+            .collect()
+    }
+
+    fn head_usable(&self, packets: &[RawIMUPacket], head: usize) -> bool {
+        self.enabled()[head]
+            && !self.msg_stale()[head]
+            && packets[head].raw_valid
+            && !self.head_isolated[head]
+    }
+
+    fn latch_isolation(&mut self, packets: &[RawIMUPacket], active: &[bool], gyro: bool) {
+        let usable: Vec<bool> = (0..self.n_imu)
+            .map(|head| self.head_usable(packets, head))
+            .collect();
+        let isolated = if gyro {
+            &mut self.gyro_isolated
+        } else {
+            &mut self.accel_isolated
+        };
+
+        // Only latch axes removed by FDI (not disabled / invalid / already isolated heads)
+        for (idx, is_active) in active.iter().enumerate() {
+            if usable[idx / 3] && !is_active {
+                isolated[idx] = true;
+            }
+        }
+
+        // Head isolated once >=2 of its axes are
+        for head in 0..self.n_imu {
+            let n_iso = isolated[3 * head..3 * head + 3]
+                .iter()
+                .filter(|iso| **iso)
+                .count();
+            if n_iso >= 2 && !self.head_isolated[head] {
+                log::warn!("IMU head {} isolated", head);
+                self.head_isolated[head] = true;
+            }
+        }
+    }
+
+    pub fn gyro_sc(&self) -> &Vector3 {
+        &self.gyro_sc
+    }
+
+    pub fn accel_sc(&self) -> &Vector3 {
+        &self.accel_sc
+    }
+}
+
+// Least squares over active axes w/ normalized parity residual test
+// Isolates the worst axis if its fault signature is unique; otherwise
+// (e.g. 2 measurements of a direction) a fault is only detected
+// Returns (fused, cov, unisolated fault) or None if unobservable
+fn fuse_fdi(
+    meas: &Generic1D,
+    h: &Generic2D,
+    active: &mut [bool],
+    sigma: f64,
+    k_thresh: f64,
+) -> Option<(Generic1D, Generic2D, bool)> {
+    loop {
+        let idx: Vec<usize> = (0..active.len()).filter(|idx| active[*idx]).collect();
+        if idx.len() < 3 {
+            return None;
+        }
+        let h_a = Generic2D::from_shape_fn((idx.len(), 3), |(row, col)| h[[idx[row], col]]);
+        let m_a = Generic1D::from_shape_fn(idx.len(), |row| meas[idx[row]]);
+
+        let hth_inv = inv(&h_a.t().dot(&h_a))?;
+        let fused = hth_inv.dot(&h_a.t().dot(&m_a));
+        let cov = &hth_inv * sigma.powi(2);
+        if idx.len() < 4 {
+            return Some((fused, cov, false));
+        }
+
+        // Residual projection into parity space: S = I - H (H^T H)^-1 H^T
+        let resid = &m_a - &h_a.dot(&fused);
+        let s = Generic2D::eye(idx.len()) - h_a.dot(&hth_inv).dot(&h_a.t());
+        let (worst, score) = (0..idx.len())
+            .map(|row| {
+                let s_jj = s[[row, row]];
+                let score = if s_jj > 1e-9 {
+                    resid[row].abs() / (sigma * s_jj.sqrt())
+                } else {
+                    0.
+                };
+                (row, score)
+            })
+            .fold(
+                (0, 0.),
+                |best, cand| if cand.1 > best.1 { cand } else { best },
+            );
+
+        if score <= k_thresh {
+            return Some((fused, cov, false));
+        }
+
+        // Isolable only if no other axis has the same parity signature (parallel
+        // column of S); aligned heads need >=3 measurements of the faulted direction
+        let s_ww = s[[worst, worst]];
+        let ambiguous = (0..idx.len()).any(|row| {
+            row != worst && s[[worst, row]].abs() > (1. - 1e-6) * (s_ww * s[[row, row]]).sqrt()
+        });
+        if ambiguous {
+            return Some((fused, cov, true));
+        }
+        log::warn!(
+            "IMU axis {} isolated; parity residual {:.1} sigma",
+            idx[worst],
+            score
+        );
+        active[idx[worst]] = false;
     }
 }
 
@@ -0,0 +1,182 @@
+use crate::estimation::types::EstimationBus;
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
+use altai_rs::{quatlib::qxform, types::*};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RawMagPacket {
+    // Timestamped field coming directly from MAG in MAG frame
+    // Meta
+    raw_timestamp: u32,
+    raw_valid: bool,
+    msg_counter: u32,
+
+    // Sensor Specific
+    raw_b_mag: [f64; 3], // Field in MAG frame [nT]
+    mtq_on: bool,        // MTQ driven during sample
+}
+impl RawSensorPacket for RawMagPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
+}
+
+impl RawMagPacket {
+    pub fn plant_update(
+        &mut self,
+        timestamp: u32,
+        raw_valid: bool,
+        inc_msg: bool,
+        raw_b_mag: [f64; 3],
+        mtq_on: bool,
+    ) {
+        self.raw_timestamp = timestamp;
+        self.raw_valid = raw_valid;
+        self.msg_counter += inc_msg as u32;
+        self.raw_b_mag = raw_b_mag;
+        self.mtq_on = mtq_on;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SensProcMagBus {
+    // Processed data coming off MAG
+    // Meta
+    meta: SensorMeta,
+    n_mag: usize,
+
+    // Sensor Specific
+    b_sc: Vector3, // Calibrated field in SC frame [T]
+    blanked: bool, // Sample held due to MTQ activity; status, not a fault
+}
+
+impl Sensor for SensProcMagBus {
+    type Packet = RawMagPacket;
+    const NAME: &'static str = "MAG";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.mag_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.mag_fdir
+    }
+
+    fn check(
+        &mut self,
+        _packets: &[Self::Packet],
+        _time_bus: &TimeBus,
+        _prev_estimation_bus: &EstimationBus,
+        param_bus: &ParamBus,
+    ) {
+        // Blanking is expected during MTQ activity; status only, field held from ingest
+        if self.blanked {
+            return;
+        }
+
+        // Check field magnitude
+        let b_max = param_bus.acs_sensors.mag_b_max;
+        let mag_check = self
+            .b_sc
+            .columns()
+            .into_iter()
+            .zip(self.enabled())
+            .all(|(b, enabled)| !enabled || b.dot(&b).sqrt() < b_max);
+        self.update_hw_test(mag_check, 4);
+    }
+
+    fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
+        let arch = &param_bus.acs_sensors;
+
+        // Blank while MTQ active on an enabled unit; hold previous field
+        self.blanked = packets[..self.n_mag]
+            .iter()
+            .zip(self.enabled())
+            .any(|(mag, enabled)| *enabled && mag.mtq_on);
+        if self.blanked {
+            return;
+        }
+
+        // Hard/soft-iron calibration in MAG frame; b_cal = S * (b_raw - h)
+        let b_raw = Vector3::from_shape_fn((3, self.n_mag), |(row, col)| {
+            packets[col].raw_b_mag[row] - arch.mag_hard_iron[row]
+        });
+        let b_cal = arch.mag_soft_iron.dot(&b_raw);
+
+        // Transform to SC frame; nT -> T
+        let tfr_mag = qxform(&arch.q_sc_mag, &b_cal) * 1e-9;
+
+        // Move to Self
+        self.b_sc.assign(&tfr_mag);
+    }
+
+    fn hardware_subtest(&self) -> u16 {
+        /* MSB
+        15
+        14
+        13
+        12
+        11
+        10
+        09
+        08
+        07
+        06
+        05
+        04: All enabled MAG field magnitude < max expected
+        03: All MAG Timestamp within sync_tol of average
+        02: >=1 enabled MAG Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
+        LSB */
+        self.health().word()
+    }
+}
+
+impl SensProcMagBus {
+    pub fn initialize(n_mag: usize) -> Self {
+        Self {
+            // Meta
+            meta: SensorMeta::initialize(n_mag),
+            n_mag,
+
+            // Sensor-Specific
+            b_sc: Vector3::zeros((3, n_mag)),
+            blanked: false,
+        }
+    }
+
+    pub fn b_sc(&self) -> &Vector3 {
+        &self.b_sc
+    }
+
+    pub fn blanked(&self) -> bool {
+        self.blanked
+    }
+}
+
+impl Default for SensProcMagBus {
+    fn default() -> Self {
+        let n_mag = 1;
+        Self::initialize(n_mag)
+    }
+}
@@ -1,5 +1,11 @@
+pub mod css;
+pub mod fdir;
 pub mod imu;
+pub mod mag;
+pub mod rwa;
+pub mod sada;
 // pub mod sensor_proc;
 pub mod gpsr;
 pub mod startracker;
+pub mod timing;
 pub mod types;
@@ -0,0 +1,223 @@
+use crate::estimation::types::EstimationBus;
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
+use altai_rs::types::*;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RawRWAPacket {
+    // Timestamped telemetry coming directly from a single wheel
+    // Meta
+    raw_timestamp: u32,
+    raw_valid: bool,
+    msg_counter: u32,
+
+    // Sensor Specific
+    raw_speed: f64,   // Tach speed about spin axis [RPM]
+    raw_current: f64, // Motor current [A]
+    raw_temp: f64,    // Motor temperature [degC]
+    raw_fault: u8,    // Wheel-reported fault bits
+}
+impl RawSensorPacket for RawRWAPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
+}
+
+impl RawRWAPacket {
+    #[allow(clippy::too_many_arguments)]
+    pub fn plant_update(
+        &mut self,
+        timestamp: u32,
+        raw_valid: bool,
+        inc_msg: bool,
+        raw_speed: f64,
+        raw_current: f64,
+        raw_temp: f64,
+        raw_fault: u8,
+    ) {
+        self.raw_timestamp = timestamp;
+        self.raw_valid = raw_valid;
+        self.msg_counter += inc_msg as u32;
+        self.raw_speed = raw_speed;
+        self.raw_current = raw_current;
+        self.raw_temp = raw_temp;
+        self.raw_fault = raw_fault;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SensProcRWABus {
+    // Processed data coming off RWA
+    // Meta
+    meta: SensorMeta,
+    n_rwa: usize,
+
+    // Sensor Specific
+    speed: Generic1D,   // Wheel speed [rad/s]
+    current: Generic1D, // Motor current [A]
+    temp: Generic1D,    // Motor temperature [degC]
+    fault: Vec<u8>,     // Wheel-reported fault bits
+    h_rw: Generic1D,    // Wheel momentum about spin axis [Nms]
+    h_rw_sc: Generic1D, // Total wheel momentum in SC frame [Nms]
+}
+
+impl Sensor for SensProcRWABus {
+    type Packet = RawRWAPacket;
+    const NAME: &'static str = "RWA";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.rwa_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.rwa_fdir
+    }
+
+    fn check(
+        &mut self,
+        _packets: &[Self::Packet],
+        _time_bus: &TimeBus,
+        _prev_estimation_bus: &EstimationBus,
+        param_bus: &ParamBus,
+    ) {
+        // Check wheel-reported faults
+        let fault_check = self
+            .fault
+            .iter()
+            .zip(self.enabled())
+            .all(|(fault, enabled)| !enabled || *fault == 0);
+        self.update_hw_test(fault_check, 4);
+
+        // Check motor temperature
+        let temp_check = self
+            .temp
+            .iter()
+            .zip(self.enabled())
+            .all(|(temp, enabled)| !enabled || *temp < param_bus.acs_sensors.rwa_temp_max);
+        self.update_hw_test(temp_check, 5);
+    }
+
+    fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
+        let arch = &param_bus.acs_actuators;
+
+        // RPM -> rad/s
+        self.speed = Generic1D::from_shape_fn(self.n_rwa, |idx| {
+            packets[idx].raw_speed * std::f64::consts::TAU / 60.
+        });
+        self.current = Generic1D::from_shape_fn(self.n_rwa, |idx| packets[idx].raw_current);
+        self.temp = Generic1D::from_shape_fn(self.n_rwa, |idx| packets[idx].raw_temp);
+        self.fault = packets[..self.n_rwa]
+            .iter()
+            .map(|rwa| rwa.raw_fault)
+            .collect();
+
+        // Momentum; spin axes from actuator architecture; disabled / invalid wheels excluded
+        self.h_rw = Generic1D::from_shape_fn(self.n_rwa, |idx| {
+            if self.usable()[idx] {
+                self.speed[idx] * arch.rw_inertia
+            } else {
+                0.
+            }
+        });
+        if arch.n_rw() == self.n_rwa {
+            self.h_rw_sc = arch.rw_axes.dot(&self.h_rw);
+        } else {
+            log::error!(
+                "RWA telemetry for {} wheels vs {} configured",
+                self.n_rwa,
+                arch.n_rw()
+            );
+            self.h_rw_sc = Generic1D::zeros(3);
+        }
+    }
+
+    fn hardware_subtest(&self) -> u16 {
+        /* MSB
+        15
+        14
+        13
+        12
+        11
+        10
+        09
+        08
+        07
+        06
+        05: All RWA Temperature < max
+        04: No RWA-reported faults
+        03: All RWA Timestamp within sync_tol of average
+        02: >=1 enabled RWA Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
+        LSB */
+        self.health().word()
+    }
+}
+
+impl SensProcRWABus {
+    pub fn initialize(n_rwa: usize) -> Self {
+        Self {
+            // Meta
+            meta: SensorMeta::initialize(n_rwa),
+            n_rwa,
+
+            // Sensor-Specific
+            speed: Generic1D::zeros(n_rwa),
+            current: Generic1D::zeros(n_rwa),
+            temp: Generic1D::zeros(n_rwa),
+            fault: vec![0u8; n_rwa],
+            h_rw: Generic1D::zeros(n_rwa),
+            h_rw_sc: Generic1D::zeros(3),
+        }
+    }
+
+    pub fn speed(&self) -> &Generic1D {
+        &self.speed
+    }
+
+    pub fn current(&self) -> &Generic1D {
+        &self.current
+    }
+
+    pub fn temp(&self) -> &Generic1D {
+        &self.temp
+    }
+
+    pub fn fault(&self) -> &[u8] {
+        &self.fault
+    }
+
+    pub fn h_rw(&self) -> &Generic1D {
+        &self.h_rw
+    }
+
+    pub fn h_rw_sc(&self) -> &Generic1D {
+        &self.h_rw_sc
+    }
+}
+
+impl Default for SensProcRWABus {
+    fn default() -> Self {
+        let n_rwa = 1;
+        Self::initialize(n_rwa)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::estimation::types::EstimationBus;
+use crate::fsw_math::wrap_angle;
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
+use altai_rs::types::*;
+use std::f64::consts::TAU;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RawSADAPacket {
+    // Timestamped resolver reading coming directly from a single SADA
+    // Meta
+    raw_timestamp: u32,
+    raw_valid: bool,
+    msg_counter: u32,
+
+    // Sensor Specific
+    raw_resolver: u32, // Resolver position [counts]
+}
+impl RawSensorPacket for RawSADAPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
+}
+
+impl RawSADAPacket {
+    pub fn plant_update(
+        &mut self,
+        timestamp: u32,
+        raw_valid: bool,
+        inc_msg: bool,
+        raw_resolver: u32,
+    ) {
+        self.raw_timestamp = timestamp;
+        self.raw_valid = raw_valid;
+        self.msg_counter += inc_msg as u32;
+        self.raw_resolver = raw_resolver;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SensProcSADABus {
+    // Processed data coming off SADA
+    // Meta
+    meta: SensorMeta,
+    n_sada: usize,
+
+    // Sensor Specific
+    angle: Generic1D, // Array angle about SADA axis, (-pi, pi] [rad]
+}
+
+impl Sensor for SensProcSADABus {
+    type Packet = RawSADAPacket;
+    const NAME: &'static str = "SADA";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.sada_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.sada_fdir
+    }
+
+    fn check(
+        &mut self,
+        _packets: &[Self::Packet],
+        _time_bus: &TimeBus,
+        _prev_estimation_bus: &EstimationBus,
+        param_bus: &ParamBus,
+    ) {
+        // Check against hard stops
+        let act = &param_bus.acs_actuators;
+        let stop_check = self
+            .angle
+            .iter()
+            .zip(self.enabled())
+            .all(|(angle, enabled)| {
+                !enabled || (act.sada_angle_min..=act.sada_angle_max).contains(angle)
+            });
+        self.update_hw_test(stop_check, 4);
+    }
+
+    fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
+        let arch = &param_bus.acs_sensors;
+
+        // Counts -> rad; wrap to (-pi, pi]
+        self.angle = Generic1D::from_shape_fn(self.n_sada, |idx| {
+            wrap_angle(
+                packets[idx].raw_resolver as f64 * TAU / arch.sada_resolver_counts as f64
+                    - arch.sada_resolver_offset,
+            )
+        });
+    }
+
+    fn hardware_subtest(&self) -> u16 {
+        /* MSB
+        15
+        14
+        13
+        12
+        11
+        10
+        09
+        08
+        07
+        06
+        05
+        04: All SADA angle within hard stops
+        03: All SADA Timestamp within sync_tol of average
+        02: >=1 enabled SADA Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
+        LSB */
+        self.health().word()
+    }
+}
+
+impl SensProcSADABus {
+    pub fn initialize(n_sada: usize) -> Self {
+        Self {
+            // Meta
+            meta: SensorMeta::initialize(n_sada),
+            n_sada,
+
+            // Sensor-Specific
+            angle: Generic1D::zeros(n_sada),
+        }
+    }
+
+    pub fn angle(&self) -> &Generic1D {
+        &self.angle
+    }
+}
+
+impl Default for SensProcSADABus {
+    fn default() -> Self {
+        let n_sada = 1;
+        Self::initialize(n_sada)
+    }
+}
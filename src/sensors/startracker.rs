@@ -1,7 +1,14 @@
 use crate::estimation::types::EstimationBus;
-use crate::{fsw_types::ParamBus, sensors::types::*};
+use crate::fsw_math::{
+    inv, qerror, qmult, qnormalize, qpositive, quat2dcm, quat2rotvec, rotvec2quat, skew, sym_eig,
+};
+use crate::time::TimeBus;
+use crate::{
+    fsw_types::ParamBus,
+    sensors::{fdir::FdirConfig, timing::SensorClock, types::*},
+};
 use altai_rs::{quatlib::qxform, types::*};
-use ndarray::{concatenate, Axis};
+use ndarray::{array, concatenate, s, Axis};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct RawStarTrackerPacket {
@@ -13,9 +20,21 @@ pub struct RawStarTrackerPacket {
 
     // Sensor-Specific
     raw_q_sta_eci: [f64; 4],
-    // TODO: ADD COV
+    raw_cov_sta: [[f64; 3]; 3], // Attitude error covariance in STA frame [rad^2]; zeros if unreported
+}
+impl RawSensorPacket for RawStarTrackerPacket {
+    fn timestamp(&self) -> u32 {
+        self.raw_timestamp
+    }
+
+    fn valid(&self) -> bool {
+        self.raw_valid
+    }
+
+    fn msg_counter(&self) -> u32 {
+        self.msg_counter
+    }
 }
-impl RawSensorPacket for RawStarTrackerPacket {}
 
 impl RawStarTrackerPacket {
     pub fn plant_update(
@@ -24,11 +43,13 @@ impl RawStarTrackerPacket {
         raw_valid: bool,
         inc_msg: bool,
         raw_q_sta_eci: [f64; 4],
+        raw_cov_sta: [[f64; 3]; 3],
     ) {
         self.raw_timestamp = timestamp;
         self.raw_valid = raw_valid;
         self.msg_counter += inc_msg as u32;
         self.raw_q_sta_eci = raw_q_sta_eci;
+        self.raw_cov_sta = raw_cov_sta;
     }
 }
 
@@ -36,84 +57,120 @@ impl RawStarTrackerPacket {
 pub struct SensProcStarTrackerBus {
     // Processed data coming off STA
     // Meta
-    timestamp: u32,
-    error_code: u16,
+    meta: SensorMeta,
     n_sta: usize,
-    prev_msg_counter: u32,
 
     // Sensor Specific
-    q_sc_eci: Quaternion4, // STA-reported attitude in SC Frame
-                           // TODO: ADD COV
+    q_sc_eci: Quaternion4,  // STA-reported attitude in SC Frame
+    cov_sc: Vec<Generic2D>, // Per-head attitude error covariance in SC frame [rad^2]
+    excluded: Vec<bool>,    // Per-head exclusion mask (invalid or angular jump)
+
+    // Fused
+    q_fused: Generic1D,   // Covariance-weighted average of non-excluded heads
+    cov_fused: Generic2D, // Fused attitude error covariance in SC frame [rad^2]
+    fused_valid: bool,
 }
 
 impl Sensor for SensProcStarTrackerBus {
     type Packet = RawStarTrackerPacket;
-    fn process(
+    const NAME: &'static str = "STA";
+
+    fn meta(&self) -> &SensorMeta {
+        &self.meta
+    }
+
+    fn meta_mut(&mut self) -> &mut SensorMeta {
+        &mut self.meta
+    }
+
+    fn clock(param_bus: &ParamBus) -> &SensorClock {
+        &param_bus.acs_sensors.sta_clock
+    }
+
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig {
+        &param_bus.acs_sensors.sta_fdir
+    }
+
+    fn check(
         &mut self,
         packets: &[Self::Packet],
-        _prev_estimation_bus: &EstimationBus,
+        time_bus: &TimeBus,
+        prev_estimation_bus: &EstimationBus,
         param_bus: &ParamBus,
     ) {
-        // Reset
-        self.error_code = 0u16;
-
-        // Check Enabled
-        let enabled = true; // TODO -> External Check
-        self.update_hw_test(enabled, 0); // HW Valid if Enabled
+        // Latency compensation; propagate each head to cycle time w/ estimated rate
+        let clock = param_bus.acs_sensors.sta_clock;
+        let stale: Vec<bool> = self.age().iter().map(|age| !clock.age_ok(*age)).collect();
+        if prev_estimation_bus.att_initialized {
+            for head in (0..self.n_sta).filter(|head| !stale[*head]) {
+                let dtheta = &prev_estimation_bus.omega_sc * self.age()[head];
+                let q_now = qnormalize(&qmult(
+                    &rotvec2quat(&dtheta),
+                    &self.q_sc_eci.column(head).to_owned(),
+                ));
+                self.q_sc_eci.column_mut(head).assign(&q_now);
+            }
+        }
 
-        // Check Message Counter
-        let msg_inc = packets.iter().fold(true, |flag, sta| {
-            flag & (sta.msg_counter != self.prev_msg_counter)
-        });
-        self.prev_msg_counter = packets[0].msg_counter;
-        self.update_hw_test(msg_inc, 1); // HW Valid if MSG Counter Incrementing
-
-        // Check Raw Valid
-        let valid = packets
-            .iter()
-            .fold(0, |acc, sta| acc + sta.raw_valid as usize)
-            > self.n_sta / 2;
-        self.update_hw_test(valid, 2); // Valid if >half STA is valid
-
-        // Check timestamp staleness
-        self.timestamp =
-            packets.iter().fold(0, |acc, sta| acc + sta.raw_timestamp) / self.n_sta as u32;
-        let valid = packets.iter().fold(true, |acc, sta| {
-            acc | ((self.timestamp as i32 - sta.raw_timestamp as i32).abs() < 10)
+        // Check each reported quaternion against propagated estimate; exclude heads individually
+        // Estimate is from the previous cycle, not the previous STA run
+        let q_prop = prev_estimation_bus.att_valid.then(|| {
+            let dtheta = &prev_estimation_bus.omega_sc * time_bus.cycle_dt();
+            qnormalize(&qmult(&rotvec2quat(&dtheta), &prev_estimation_bus.q_sc_eci))
         });
-        self.update_hw_test(valid, 3); // Valid if each timestamp within 1 sec of average
-
-        // Update Data
-        self.ingest(packets, param_bus);
-
-        // Check reported quaternion
-        let ang_check = true; // TODO: implement angular check against prev est bus
+        self.excluded = (0..self.n_sta)
+            .map(|head| {
+                let jump = q_prop.as_ref().is_some_and(|q_prop| {
+                    let dq = qerror(&self.q_sc_eci.column(head).to_owned(), q_prop);
+                    let angle = quat2rotvec(&dq);
+                    angle.dot(&angle).sqrt() > param_bus.acs_sensors.sta_ang_max
+                });
+                if jump {
+                    log::warn!(
+                        "STA head {} excluded; angular jump from propagated estimate",
+                        head
+                    );
+                }
+                if stale[head] {
+                    log::debug!("STA head {} excluded; age {:.3} s", head, self.age()[head]);
+                }
+                jump || stale[head]
+                    || self.msg_stale()[head]
+                    || !packets[head].raw_valid
+                    || !self.enabled()[head]
+                    || sta_mount(&param_bus.acs_sensors.q_sc_sta, head).is_none()
+            })
+            .collect();
+        let ang_check = self.excluded.iter().any(|excluded| !excluded);
         self.update_hw_test(ang_check, 4);
+
+        // Fuse non-excluded heads
+        self.fuse();
+        self.update_hw_test(self.fused_valid, 5);
     }
 
     fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus) {
-        // Transform to SC frame
-        let tfr_sta = qxform(
-            &param_bus.acs_sensors.q_sc_sta,
-            &Quaternion4::from_shape_fn((4, self.n_sta), |(row, col)| {
-                packets[col].raw_q_sta_eci[row]
-            }),
-        );
+        // Transform each head to SC frame w/ its own mount; unmounted heads excluded in check
+        let cov_default = Generic2D::eye(3) * param_bus.acs_estimation.sigma_sta.powi(2);
+        self.cov_sc = vec![cov_default.to_owned(); self.n_sta];
+        for (head, sta) in packets[..self.n_sta].iter().enumerate() {
+            let Some(q_sc_sta) = sta_mount(&param_bus.acs_sensors.q_sc_sta, head) else {
+                log::error!("No mount configured for STA head {}", head);
+                continue;
+            };
+            let tfr_sta = qxform(
+                &q_sc_sta,
+                &Quaternion4::from_shape_fn((4, 1), |(row, _)| sta.raw_q_sta_eci[row]),
+            );
+            self.q_sc_eci.column_mut(head).assign(&tfr_sta.column(0));
 
-        // Move to Self
-        self.q_sc_eci.assign(&tfr_sta);
-
-        // Get timestamp average
-        self.timestamp =
-            packets.iter().fold(0, |acc, sta| acc + sta.raw_timestamp) / self.n_sta as u32;
-
-        // Valid if >half of stas are valid
-        let valid = packets
-            .iter()
-            .fold(0, |acc, sta| acc + sta.raw_valid as usize)
-            > self.n_sta / 2;
-
-        self.error_code = (!valid) as u16; // TODO: FIX
+            // Rotate covariance to SC frame; A_h P A_h^T; isotropic noise if unreported
+            let a_sc_sta = quat2dcm(&q_sc_sta.column(0).to_owned());
+            let cov_sta = Generic2D::from_shape_fn((3, 3), |(row, col)| sta.raw_cov_sta[row][col]);
+            if cov_sta.diag().iter().all(|var| *var > 0.) {
+                self.cov_sc[head] = a_sc_sta.dot(&cov_sta).dot(&a_sc_sta.t());
+            }
+        }
     }
 
     fn hardware_subtest(&self) -> u16 {
@@ -128,40 +185,103 @@ impl Sensor for SensProcStarTrackerBus {
         08
         07
         06
-        05
-        04: Reported quaternion < 10 deg from previous reading
-        03: All STA Timestamp < 1 sec from average
-        02: >n/2 STA Valid
-        01: MsgCounter Increasing
-        00: Enabled
+        05: Fused attitude available
+        04: >=1 STA quaternion current and within max angle of propagated estimate
+        03: All STA Timestamp within sync_tol of average
+        02: >=1 enabled STA Valid
+        01: MsgCounter Increasing (>=1 enabled unit)
+        00: >=1 Enabled
         LSB */
-        self.error_code
+        self.health().word()
     }
 }
 
 impl SensProcStarTrackerBus {
     pub fn initialize(n_sta: usize) -> Self {
         Self {
-            timestamp: 0,
-            error_code: 0u16,
-            prev_msg_counter: 0u32,
+            // Meta
+            meta: SensorMeta::initialize(n_sta),
             n_sta,
             q_sc_eci: concatenate![
                 Axis(0),
                 Generic2D::zeros((3, n_sta)),
                 Generic2D::ones((1, n_sta))
             ],
+            cov_sc: vec![Generic2D::eye(3); n_sta],
+            excluded: vec![false; n_sta],
+            q_fused: array![0., 0., 0., 1.],
+            cov_fused: Generic2D::eye(3),
+            fused_valid: false,
         }
     }
 
-    fn update_hw_test(&mut self, flag: bool, bit_id: u8) {
-        if bit_id > 15 {
-            panic!("Invalid bit setting for u16 bitpack")
+    pub fn q_sc_eci(&self) -> &Quaternion4 {
+        &self.q_sc_eci
+    }
+
+    pub fn cov_sc(&self) -> &[Generic2D] {
+        &self.cov_sc
+    }
+
+    pub fn excluded(&self) -> &[bool] {
+        &self.excluded
+    }
+
+    pub fn q_fused(&self) -> &Generic1D {
+        &self.q_fused
+    }
+
+    pub fn cov_fused(&self) -> &Generic2D {
+        &self.cov_fused
+    }
+
+    pub fn fused_valid(&self) -> bool {
+        self.fused_valid
+    }
+
+    // Covariance-weighted quaternion average (Markley et al., 2007)
+    // Minimizes sum(dtheta_i^T P_i^-1 dtheta_i) w/ dtheta_i ~ 2 G(q_i) q_avg, where
+    // G(q) q_avg is the vector part of q ⊗ q_avg^-1; solution is the eigenvector of
+    // M = sum(G_i^T P_i^-1 G_i) w/ the smallest eigenvalue
+    fn fuse(&mut self) {
+        let mut m = Generic2D::zeros((4, 4));
+        let mut info = Generic2D::zeros((3, 3));
+        let mut n_used = 0;
+        for head in (0..self.n_sta).filter(|head| !self.excluded[*head]) {
+            let Some(cov_inv) = inv(&self.cov_sc[head]) else {
+                log::warn!("STA head {} covariance singular; not fused", head);
+                continue;
+            };
+            let q = qnormalize(&self.q_sc_eci.column(head).to_owned());
+            let mut g = Generic2D::zeros((3, 4));
+            g.slice_mut(s![.., 0..3])
+                .assign(&(skew(&q.slice(s![0..3]).to_owned()) - Generic2D::eye(3) * q[3]));
+            g.slice_mut(s![.., 3]).assign(&q.slice(s![0..3]));
+
+            m = m + g.t().dot(&cov_inv).dot(&g);
+            info = info + cov_inv;
+            n_used += 1;
+        }
+
+        let cov_fused = inv(&info);
+        self.fused_valid = n_used > 0 && cov_fused.is_some();
+        if let (true, Some(cov_fused)) = (self.fused_valid, cov_fused) {
+            let (eig_val, eig_vec) = sym_eig(&m);
+            let min_idx = (0..4)
+                .min_by(|&i, &j| eig_val[i].total_cmp(&eig_val[j]))
+                .unwrap();
+            self.q_fused = qpositive(&qnormalize(&eig_vec.column(min_idx).to_owned()));
+            self.cov_fused = cov_fused;
         }
-        self.error_code ^= (0b1 * !flag as u16) << bit_id;
     }
 }
 
+// Mount of one head (4x1); a single column is shared by every head
+fn sta_mount(q_sc_sta: &Quaternion4, head: usize) -> Option<Quaternion4> {
+    let col = if q_sc_sta.ncols() == 1 { 0 } else { head };
+    (col < q_sc_sta.ncols()).then(|| q_sc_sta.slice(s![.., col..col + 1]).to_owned())
+}
+
 impl Default for SensProcStarTrackerBus {
     fn default() -> Self {
         let n_sta = 1;
@@ -0,0 +1,40 @@
+// Sensor clock -> FSW clock
+// t_fsw = offset + tick * raw_timestamp - latency
+#[derive(Clone, Copy, Debug)]
+pub struct SensorClock {
+    pub tick: f64,     // Sensor clock resolution [s/count]
+    pub offset: f64,   // FSW time at sensor count zero [s]
+    pub latency: f64,  // Time of validity before the stamped time [s]
+    pub max_age: f64,  // Oldest measurement compensated to cycle time [s]
+    pub sync_tol: f64, // Max unit timestamp spread from the average [s]
+}
+impl SensorClock {
+    pub fn new(tick: f64, offset: f64, latency: f64, max_age: f64, sync_tol: f64) -> Self {
+        Self {
+            tick,
+            offset,
+            latency,
+            max_age,
+            sync_tol,
+        }
+    }
+
+    // Measurement time of validity on FSW clock [s]
+    pub fn to_fsw(&self, raw_timestamp: u32) -> f64 {
+        self.offset + self.tick * raw_timestamp as f64 - self.latency
+    }
+
+    // Time since validity at FSW time [s]; jump_offset: FSW clock jumps since offset was set [s]
+    pub fn age(&self, raw_timestamp: u32, fsw_time: f64, jump_offset: f64) -> f64 {
+        fsw_time - jump_offset - self.to_fsw(raw_timestamp)
+    }
+
+    pub fn age_ok(&self, age: f64) -> bool {
+        (0.0..=self.max_age).contains(&age)
+    }
+}
+impl Default for SensorClock {
+    fn default() -> Self {
+        Self::new(0.1, 0., 0., 1., 1.)
+    }
+}
@@ -1,20 +1,38 @@
+use super::css::{RawCSSPacket, SensProcCSSBus};
+use super::fdir::{FdirConfig, FdirMonitor};
 use super::gpsr::{RawGPSRPacket, SensProcGPSRBus};
 use super::imu::SensProcIMUBus;
+use super::mag::{RawMagPacket, SensProcMagBus};
+use super::rwa::{RawRWAPacket, SensProcRWABus};
+use super::sada::{RawSADAPacket, SensProcSADABus};
 use super::startracker::{RawStarTrackerPacket, SensProcStarTrackerBus};
+use super::timing::SensorClock;
 use crate::fsw_types::ParamBus;
+use crate::scheduler::{Phase, RateGroup, Scheduler};
 use crate::sensors::imu::RawIMUPacket;
+use altai_rs::meta::types::Generic1D;
+use ndarray::Axis;
 
 use crate::estimation::types::EstimationBus;
+use crate::time::TimeBus;
 
 const MAX_IMU: usize = 12;
 const MAX_STA: usize = 4;
-const MAX_GPSR: usize = 1;
+const MAX_GPSR: usize = 3;
+const MAX_MAG: usize = 3;
+const MAX_CSS: usize = 16;
+const MAX_RWA: usize = 6;
+const MAX_SADA: usize = 2;
 
 #[derive(Clone, Debug)]
 pub struct RawSensorBus {
     raw_imu_bus: [RawIMUPacket; MAX_IMU],
     raw_sta_bus: [RawStarTrackerPacket; MAX_STA],
     raw_gpsr_bus: [RawGPSRPacket; MAX_GPSR],
+    raw_mag_bus: [RawMagPacket; MAX_MAG],
+    raw_css_bus: [RawCSSPacket; MAX_CSS],
+    raw_rwa_bus: [RawRWAPacket; MAX_RWA],
+    raw_sada_bus: [RawSADAPacket; MAX_SADA],
 }
 
 impl Default for RawSensorBus {
@@ -23,22 +41,270 @@ impl Default for RawSensorBus {
             raw_imu_bus: [RawIMUPacket::default(); MAX_IMU],
             raw_sta_bus: [RawStarTrackerPacket::default(); MAX_STA],
             raw_gpsr_bus: [RawGPSRPacket::default(); MAX_GPSR],
+            raw_mag_bus: [RawMagPacket::default(); MAX_MAG],
+            raw_css_bus: [RawCSSPacket::default(); MAX_CSS],
+            raw_rwa_bus: [RawRWAPacket::default(); MAX_RWA],
+            raw_sada_bus: [RawSADAPacket::default(); MAX_SADA],
         }
     }
 }
 
-pub trait RawSensorPacket {}
+pub trait RawSensorPacket {
+    fn timestamp(&self) -> u32; // Sensor clock [counts]
+    fn valid(&self) -> bool;
+    fn msg_counter(&self) -> u32;
+}
+
+// Processing state common to every sensor
+#[derive(Clone, Debug)]
+pub struct SensorMeta {
+    timestamp: u32,
+    health: FdirMonitor,
+    prev_msg_counter: Vec<Option<u32>>, // Per-unit last counter; None until first message
+    missed_msgs: Vec<u32>,              // Per-unit messages skipped by counter
+    msg_stale: Vec<bool>,               // Per-unit counter did not advance this cycle
+    enabled: Vec<bool>,                 // Per-unit commanded enable
+    usable: Vec<bool>,                  // Per-unit enabled, valid and counter advancing
+    age: Generic1D,                     // Per-unit measurement age at cycle time [s]
+    cycle: u64,                         // GNC cycle last processed
+}
+
+impl SensorMeta {
+    pub fn initialize(n_units: usize) -> Self {
+        Self {
+            timestamp: 0,
+            health: FdirMonitor::default(),
+            prev_msg_counter: vec![None; n_units],
+            missed_msgs: vec![0; n_units],
+            msg_stale: vec![false; n_units],
+            enabled: vec![true; n_units],
+            usable: vec![false; n_units],
+            age: Generic1D::zeros(n_units),
+            cycle: 0,
+        }
+    }
+
+    // Common health bits 00-03; false if no unit is enabled
+    fn validate<P: RawSensorPacket>(
+        &mut self,
+        name: &str,
+        packets: &[P],
+        time_bus: &TimeBus,
+        clock: &SensorClock,
+    ) -> bool {
+        let n_units = self.enabled.len();
+
+        // Check Enabled; disabled units are excluded from voting
+        let active: Vec<&P> = packets[..n_units]
+            .iter()
+            .zip(&self.enabled)
+            .filter_map(|(unit, enabled)| enabled.then_some(unit))
+            .collect();
+        self.health.update(!active.is_empty(), 0); // HW Valid if >=1 Enabled
+        if active.is_empty() {
+            self.usable.fill(false);
+            return false;
+        }
+
+        // Check Message Counter per unit; wrapping difference handles u32 rollover
+        for (idx, unit) in packets[..n_units].iter().enumerate() {
+            if !self.enabled[idx] {
+                continue;
+            }
+            let counter = unit.msg_counter();
+            self.msg_stale[idx] = match self.prev_msg_counter[idx] {
+                Some(prev) => {
+                    let delta = counter.wrapping_sub(prev);
+                    if delta > u32::MAX / 2 {
+                        // Counter went backwards; unit reset, not missed messages
+                        log::warn!(
+                            "{} unit {} message counter reset ({} -> {})",
+                            name,
+                            idx,
+                            prev,
+                            counter
+                        );
+                    } else if delta > 1 {
+                        self.missed_msgs[idx] = self.missed_msgs[idx].saturating_add(delta - 1);
+                    }
+                    delta == 0
+                }
+                None => false,
+            };
+            self.prev_msg_counter[idx] = Some(counter);
+        }
+        // Single stale / invalid units are excluded per unit (msg_stale, usable), not failed
+        let msg_inc = (0..n_units).any(|idx| self.enabled[idx] && !self.msg_stale[idx]);
+        self.health.update(msg_inc, 1); // HW Valid if >=1 enabled MSG Counter Incrementing
+
+        // Check Raw Valid
+        let valid = active.iter().any(|unit| unit.valid());
+        self.health.update(valid, 2); // Valid if >=1 enabled unit is valid
+        self.usable = (0..n_units)
+            .map(|idx| self.enabled[idx] && !self.msg_stale[idx] && packets[idx].valid())
+            .collect();
+
+        // Check timestamp staleness; average in u64 counts, spread on FSW clock
+        let n_active = active.len() as u64;
+        self.timestamp = (active
+            .iter()
+            .map(|unit| unit.timestamp() as u64)
+            .sum::<u64>()
+            / n_active) as u32;
+        let t_unit: Vec<f64> = active
+            .iter()
+            .map(|unit| clock.to_fsw(unit.timestamp()))
+            .collect();
+        let t_mean = t_unit.iter().sum::<f64>() / t_unit.len() as f64;
+        let valid = t_unit.iter().all(|t| (t - t_mean).abs() < clock.sync_tol);
+        self.health.update(valid, 3); // Valid if each timestamp within sync_tol of average
+
+        // Measurement age on FSW clock
+        self.age = Generic1D::from_shape_fn(n_units, |idx| {
+            clock.age(
+                packets[idx].timestamp(),
+                time_bus.fsw_time(),
+                time_bus.jump_offset(),
+            )
+        });
+        true
+    }
+}
+
+impl Default for SensorMeta {
+    fn default() -> Self {
+        Self::initialize(1)
+    }
+}
 
+// Implementors supply ingest + sensor-specific checks (bits 04-15); the common
+// enable / message counter / validity / timestamp checks (bits 00-03) are shared
 pub trait Sensor {
     type Packet: RawSensorPacket;
+    const NAME: &'static str;
+
+    fn meta(&self) -> &SensorMeta;
+    fn meta_mut(&mut self) -> &mut SensorMeta;
+    fn clock(param_bus: &ParamBus) -> &SensorClock;
+    fn fdir_config(param_bus: &ParamBus) -> &FdirConfig;
+
     fn ingest(&mut self, packets: &[Self::Packet], param_bus: &ParamBus); // Convert from sensor units to SI units; SC/ECI frame
-    fn process(
+    fn check(
         &mut self,
         packets: &[Self::Packet],
+        time_bus: &TimeBus,
         prev_estimation_bus: &EstimationBus,
         param_bus: &ParamBus,
-    ); // Check for validity, set hw subtest
+    ); // Sensor-specific checks on ingested data
     fn hardware_subtest(&self) -> u16;
+
+    // Check for validity, set hw subtest
+    fn process(
+        &mut self,
+        packets: &[Self::Packet],
+        time_bus: &TimeBus,
+        prev_estimation_bus: &EstimationBus,
+        param_bus: &ParamBus,
+    ) {
+        // Persistence / latching per health bit
+        let meta = self.meta_mut();
+        meta.cycle = time_bus.cycle();
+        meta.health.configure(Self::fdir_config(param_bus));
+        if !meta.validate(Self::NAME, packets, time_bus, Self::clock(param_bus)) {
+            return;
+        }
+
+        // Update Data
+        self.ingest(packets, param_bus);
+        self.check(packets, time_bus, prev_estimation_bus, param_bus);
+    }
+
+    fn update_hw_test(&mut self, flag: bool, bit_id: u8) {
+        self.meta_mut().health.update(flag, bit_id);
+    }
+
+    fn timestamp(&self) -> u32 {
+        self.meta().timestamp
+    }
+
+    fn health(&self) -> &FdirMonitor {
+        &self.meta().health
+    }
+
+    fn age(&self) -> &Generic1D {
+        &self.meta().age
+    }
+
+    // Processed this cycle; slower rate groups hold their last outputs in between
+    fn fresh(&self, time_bus: &TimeBus) -> bool {
+        self.meta().cycle == time_bus.cycle()
+    }
+
+    fn missed_msgs(&self) -> &[u32] {
+        &self.meta().missed_msgs
+    }
+
+    fn msg_stale(&self) -> &[bool] {
+        &self.meta().msg_stale
+    }
+
+    fn enabled(&self) -> &[bool] {
+        &self.meta().enabled
+    }
+
+    fn usable(&self) -> &[bool] {
+        &self.meta().usable
+    }
+
+    // Persists until re-commanded
+    fn command_enable(&mut self, unit: usize, enable: bool) {
+        let meta = self.meta_mut();
+        let n_units = meta.enabled.len();
+        match meta.enabled.get_mut(unit) {
+            Some(enabled) => {
+                // Counter restarts on re-enable; messages sent while off are not missed
+                if enable && !*enabled {
+                    meta.prev_msg_counter[unit] = None;
+                    meta.msg_stale[unit] = false;
+                }
+                *enabled = enable;
+            }
+            None => log::error!(
+                "No {} unit {} to command; {} configured",
+                Self::NAME,
+                unit,
+                n_units
+            ),
+        }
+    }
+
+    // Clears latched faults on the masked bits
+    fn command_clear_faults(&mut self, mask: u16) {
+        self.meta_mut().health.command_clear(mask);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorType {
+    IMU,
+    STA,
+    GPSR,
+    MAG,
+    CSS,
+    RWA,
+    SADA,
+}
+
+#[derive(Clone, Debug)]
+pub struct SensorHealthTlm {
+    pub sensor: SensorType,
+    pub health: u16,             // hardware_subtest word
+    pub latched: u16,            // Latched fault bits
+    pub enabled: Vec<bool>,      // Per-unit commanded enable
+    pub msg_stale: Vec<bool>,    // Per-unit counter did not advance last cycle
+    pub missed_msgs: Vec<u32>,   // Per-unit messages skipped by counter
+    pub unit_flags: Vec<u8>,     // Per-unit sensor-specific flags (GPSR RX_*); empty if none
+    pub selected: Option<usize>, // Unit in use (GPSR)
 }
 
 #[derive(Clone, Debug, Default)]
@@ -49,14 +315,34 @@ pub struct SensorBus {
     sta_available: bool,
     gpsr_bus: SensProcGPSRBus,
     gpsr_available: bool,
+    mag_bus: SensProcMagBus,
+    mag_available: bool,
+    css_bus: SensProcCSSBus,
+    css_available: bool,
+    rwa_bus: SensProcRWABus,
+    rwa_available: bool,
+    sada_bus: SensProcSADABus,
+    sada_available: bool,
 }
 
 impl SensorBus {
-    pub fn initialize(n_imu: usize, n_sta: usize, n_gpsr: usize) -> Self {
+    pub fn initialize(
+        n_imu: usize,
+        n_sta: usize,
+        n_gpsr: usize,
+        n_mag: usize,
+        n_css: usize,
+        n_rwa: usize,
+        n_sada: usize,
+    ) -> Self {
         // Check against max supported
         let n_imu = Self::check_max(n_imu, MAX_IMU, "IMUs");
-        let n_sta = Self::check_max(n_sta, MAX_IMU, "STAs");
-        let n_gpsr = Self::check_max(n_gpsr, MAX_IMU, "GPSRs");
+        let n_sta = Self::check_max(n_sta, MAX_STA, "STAs");
+        let n_gpsr = Self::check_max(n_gpsr, MAX_GPSR, "GPSRs");
+        let n_mag = Self::check_max(n_mag, MAX_MAG, "MAGs");
+        let n_css = Self::check_max(n_css, MAX_CSS, "CSSs");
+        let n_rwa = Self::check_max(n_rwa, MAX_RWA, "RWAs");
+        let n_sada = Self::check_max(n_sada, MAX_SADA, "SADAs");
 
         Self {
             imu_bus: SensProcIMUBus::initialize(n_imu),
@@ -65,36 +351,312 @@ impl SensorBus {
             sta_available: n_sta > 0,
             gpsr_bus: SensProcGPSRBus::initialize(n_gpsr),
             gpsr_available: n_gpsr > 0,
+            mag_bus: SensProcMagBus::initialize(n_mag),
+            mag_available: n_mag > 0,
+            css_bus: SensProcCSSBus::initialize(n_css),
+            css_available: n_css > 0,
+            rwa_bus: SensProcRWABus::initialize(n_rwa),
+            rwa_available: n_rwa > 0,
+            sada_bus: SensProcSADABus::initialize(n_sada),
+            sada_available: n_sada > 0,
         }
     }
 
-    pub fn process(
+    // Single sensor; run from its rate group
+    pub fn process_sensor(
         &mut self,
+        sensor: SensorType,
         raw_sensor_data: &RawSensorBus,
+        time_bus: &TimeBus,
         prev_est_bus: &EstimationBus,
         param_bus: &ParamBus,
     ) {
-        // Update IMU
-        if self.imu_available {
-            self.imu_bus
-                .process(&raw_sensor_data.raw_imu_bus, prev_est_bus, param_bus);
+        match sensor {
+            SensorType::IMU if self.imu_available => self.imu_bus.process(
+                &raw_sensor_data.raw_imu_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            SensorType::STA if self.sta_available => self.sta_bus.process(
+                &raw_sensor_data.raw_sta_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            SensorType::GPSR if self.gpsr_available => self.gpsr_bus.process(
+                &raw_sensor_data.raw_gpsr_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            SensorType::MAG if self.mag_available => self.mag_bus.process(
+                &raw_sensor_data.raw_mag_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            SensorType::CSS if self.css_available => self.css_bus.process(
+                &raw_sensor_data.raw_css_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            // Tach + TLM
+            SensorType::RWA if self.rwa_available => self.rwa_bus.process(
+                &raw_sensor_data.raw_rwa_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            SensorType::SADA if self.sada_available => self.sada_bus.process(
+                &raw_sensor_data.raw_sada_bus,
+                time_bus,
+                prev_est_bus,
+                param_bus,
+            ),
+            _ => {}
         }
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        scheduler.register(
+            "IMU",
+            RateGroup::FAST,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::IMU,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "STA",
+            RateGroup::STA,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::STA,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "GPSR",
+            RateGroup::NAV,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::GPSR,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "MAG",
+            RateGroup::FAST,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::MAG,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "CSS",
+            RateGroup::FAST,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::CSS,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "RWA",
+            RateGroup::FAST,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::RWA,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "SADA",
+            RateGroup::FAST,
+            Phase::SENSORS,
+            |curr, prev, time_bus, param_bus| {
+                curr.tlm_sensor_bus.process_sensor(
+                    SensorType::SADA,
+                    &curr.raw_sensor_bus,
+                    time_bus,
+                    &prev.estimation_bus,
+                    param_bus,
+                )
+            },
+        );
+        scheduler.register(
+            "SENSOR_HK",
+            RateGroup::HOUSEKEEPING,
+            Phase::HOUSEKEEPING,
+            |curr, _, _, _| curr.tlm_sensor_bus.housekeeping(),
+        );
+    }
 
-        // Update STA
-        if self.sta_available {
-            self.sta_bus
-                .process(&raw_sensor_data.raw_sta_bus, prev_est_bus, param_bus);
+    // Health summary per sensor
+    pub fn housekeeping(&self) {
+        for tlm in self.health_tlm() {
+            log::info!(
+                "{:?} health {:#06x} latched {:#06x}; missed msgs {:?}",
+                tlm.sensor,
+                tlm.health,
+                tlm.latched,
+                tlm.missed_msgs
+            );
         }
+    }
 
-        // Update GPSR
-        if self.gpsr_available {
-            self.gpsr_bus
-                .process(&raw_sensor_data.raw_gpsr_bus, prev_est_bus, param_bus);
+    // Health telemetry per available sensor
+    pub fn health_tlm(&self) -> Vec<SensorHealthTlm> {
+        fn tlm<S: Sensor>(sensor: SensorType, bus: Option<&S>) -> Option<SensorHealthTlm> {
+            bus.map(|bus| SensorHealthTlm {
+                sensor,
+                health: bus.health().word(),
+                latched: bus.health().latched(),
+                enabled: bus.enabled().to_vec(),
+                msg_stale: bus.msg_stale().to_vec(),
+                missed_msgs: bus.missed_msgs().to_vec(),
+                unit_flags: Vec::new(),
+                selected: None,
+            })
         }
+        [
+            tlm(SensorType::IMU, self.imu_bus()),
+            tlm(SensorType::STA, self.sta_bus()),
+            tlm(SensorType::GPSR, self.gpsr_bus()).map(|tlm| {
+                let gpsr = &self.gpsr_bus;
+                SensorHealthTlm {
+                    unit_flags: gpsr.receiver_health().to_vec(),
+                    selected: gpsr.selected(),
+                    ..tlm
+                }
+            }),
+            tlm(SensorType::MAG, self.mag_bus()),
+            tlm(SensorType::CSS, self.css_bus()),
+            tlm(SensorType::RWA, self.rwa_bus()),
+            tlm(SensorType::SADA, self.sada_bus()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 
-        // TODO: Add CSS
-        // TODO: Add SADA
-        // TODO: Add RWA (Tach + TLM)
+    pub fn imu_bus(&self) -> Option<&SensProcIMUBus> {
+        self.imu_available.then_some(&self.imu_bus)
+    }
+
+    pub fn sta_bus(&self) -> Option<&SensProcStarTrackerBus> {
+        self.sta_available.then_some(&self.sta_bus)
+    }
+
+    pub fn gpsr_bus(&self) -> Option<&SensProcGPSRBus> {
+        self.gpsr_available.then_some(&self.gpsr_bus)
+    }
+
+    pub fn mag_bus(&self) -> Option<&SensProcMagBus> {
+        self.mag_available.then_some(&self.mag_bus)
+    }
+
+    // Measured magnetic field in SC frame [T]; last unblanked sample while MTQ active
+    pub fn b_sc(&self) -> Option<Generic1D> {
+        self.mag_bus()
+            .filter(|mag| mag.hardware_subtest() == 0)
+            .and_then(|mag| {
+                // Average over usable magnetometers; none before bit 0 persists
+                let cols: Vec<usize> = (0..mag.b_sc().ncols())
+                    .filter(|&col| mag.usable()[col])
+                    .collect();
+                mag.b_sc().select(Axis(1), &cols).mean_axis(Axis(1))
+            })
+    }
+
+    pub fn css_bus(&self) -> Option<&SensProcCSSBus> {
+        self.css_available.then_some(&self.css_bus)
+    }
+
+    // Measured sun unit vector in SC frame
+    pub fn u_sun_sc(&self) -> Option<Generic1D> {
+        self.css_bus()
+            .filter(|css| css.hardware_subtest() == 0 && css.sun_visible())
+            .map(|css| css.u_sun_sc().to_owned())
+    }
+
+    pub fn rwa_bus(&self) -> Option<&SensProcRWABus> {
+        self.rwa_available.then_some(&self.rwa_bus)
+    }
+
+    pub fn sada_bus(&self) -> Option<&SensProcSADABus> {
+        self.sada_available.then_some(&self.sada_bus)
+    }
+
+    // Per-unit enable; persists across cycles until re-commanded
+    pub fn command_enable(&mut self, sensor: SensorType, unit: usize, enable: bool) {
+        log::info!(
+            "{:?} unit {} {}",
+            sensor,
+            unit,
+            if enable { "enabled" } else { "disabled" }
+        );
+        match sensor {
+            SensorType::IMU => self.imu_bus.command_enable(unit, enable),
+            SensorType::STA => self.sta_bus.command_enable(unit, enable),
+            SensorType::GPSR => self.gpsr_bus.command_enable(unit, enable),
+            SensorType::MAG => self.mag_bus.command_enable(unit, enable),
+            SensorType::CSS => self.css_bus.command_enable(unit, enable),
+            SensorType::RWA => self.rwa_bus.command_enable(unit, enable),
+            SensorType::SADA => self.sada_bus.command_enable(unit, enable),
+        }
+    }
+
+    // Clears persisted + latched faults on the masked health bits; IMU bits 4/5 also
+    // restore isolated gyro / accel axes
+    pub fn command_clear_faults(&mut self, sensor: SensorType, mask: u16) {
+        log::info!("{:?} faults cleared; mask {:#06x}", sensor, mask);
+        match sensor {
+            SensorType::IMU => {
+                self.imu_bus.command_clear_faults(mask);
+                self.imu_bus.clear_isolation(mask);
+            }
+            SensorType::STA => self.sta_bus.command_clear_faults(mask),
+            SensorType::GPSR => self.gpsr_bus.command_clear_faults(mask),
+            SensorType::MAG => self.mag_bus.command_clear_faults(mask),
+            SensorType::CSS => self.css_bus.command_clear_faults(mask),
+            SensorType::RWA => self.rwa_bus.command_clear_faults(mask),
+            SensorType::SADA => self.sada_bus.command_clear_faults(mask),
+        }
     }
 
     fn check_max(n_init: usize, max: usize, name: &str) -> usize {
@@ -0,0 +1,427 @@
+use crate::fsw_types::{Param, ParamBus};
+use crate::scheduler::{Phase, RateGroup, Scheduler};
+use crate::sensors::types::{Sensor, SensorBus};
+
+// Time scales are carried as seconds since the GPS epoch (1980-01-06 00:00:00)
+// on their own scale; TAI = GPS + 19 s, TT = TAI + 32.184 s, UTC = TAI - leap
+
+pub const JD_GPS_EPOCH: f64 = 2444244.5;
+pub const MJD_GPS_EPOCH: f64 = 44244.;
+pub const TAI_GPS: f64 = 19.; // TAI - GPS [s]
+pub const TAI_UTC_PRE: f64 = 19.; // TAI - UTC before the first table entry [s]
+pub const TT_TAI: f64 = 32.184; // TT - TAI [s]
+const SEC_PER_WEEK: f64 = 604800.;
+const SEC_PER_DAY: f64 = 86400.;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GpsTime {
+    pub week: u32,    // Weeks since GPS epoch (unrolled)
+    pub seconds: f64, // Seconds of week [s]
+}
+impl GpsTime {
+    pub fn new(week: u32, seconds: f64) -> Self {
+        Self { week, seconds }
+    }
+
+    pub fn from_seconds(gps_seconds: f64) -> Self {
+        let week = (gps_seconds / SEC_PER_WEEK).floor();
+        Self::new(week as u32, gps_seconds - week * SEC_PER_WEEK)
+    }
+
+    // Seconds since GPS epoch [s]
+    pub fn to_seconds(&self) -> f64 {
+        self.week as f64 * SEC_PER_WEEK + self.seconds
+    }
+}
+
+// TAI - UTC by UTC effective date; uploadable as IERS announces leap seconds
+#[derive(Clone, Debug)]
+pub struct LeapSeconds {
+    table: Vec<(f64, f64)>, // (MJD UTC effective, TAI - UTC [s]), ascending
+}
+impl LeapSeconds {
+    pub fn new(table: &[(f64, f64)]) -> Self {
+        let mut table = table.to_vec();
+        table.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { table }
+    }
+
+    // TAI - UTC at TAI [s]
+    pub fn tai_utc(&self, tai: f64) -> f64 {
+        self.table
+            .iter()
+            .rev()
+            .find(|(mjd, leap)| mjd2seconds(*mjd) + leap <= tai)
+            .map_or(TAI_UTC_PRE, |(_, leap)| *leap)
+    }
+
+    // TAI - UTC at UTC [s]
+    pub fn tai_utc_at_utc(&self, utc: f64) -> f64 {
+        self.table
+            .iter()
+            .rev()
+            .find(|(mjd, _)| mjd2seconds(*mjd) <= utc)
+            .map_or(TAI_UTC_PRE, |(_, leap)| *leap)
+    }
+}
+impl Default for LeapSeconds {
+    // Leap seconds since GPS epoch through 2017-01-01
+    fn default() -> Self {
+        Self::new(&[
+            (44239., 19.),
+            (44786., 20.),
+            (45151., 21.),
+            (45516., 22.),
+            (46247., 23.),
+            (47161., 24.),
+            (47892., 25.),
+            (48257., 26.),
+            (48804., 27.),
+            (49169., 28.),
+            (49534., 29.),
+            (50083., 30.),
+            (50630., 31.),
+            (51179., 32.),
+            (53736., 33.),
+            (54832., 34.),
+            (56109., 35.),
+            (57204., 36.),
+            (57754., 37.),
+        ])
+    }
+}
+
+// Scale conversions; all in seconds since GPS epoch
+pub fn gps2tai(gps: f64) -> f64 {
+    gps + TAI_GPS
+}
+
+pub fn tai2gps(tai: f64) -> f64 {
+    tai - TAI_GPS
+}
+
+pub fn tai2tt(tai: f64) -> f64 {
+    tai + TT_TAI
+}
+
+pub fn tai2utc(tai: f64, leap: &LeapSeconds) -> f64 {
+    tai - leap.tai_utc(tai)
+}
+
+pub fn utc2tai(utc: f64, leap: &LeapSeconds) -> f64 {
+    utc + leap.tai_utc_at_utc(utc)
+}
+
+// Julian date on the same scale
+pub fn jd(seconds: f64) -> f64 {
+    JD_GPS_EPOCH + seconds / SEC_PER_DAY
+}
+
+fn mjd2seconds(mjd: f64) -> f64 {
+    (mjd - MJD_GPS_EPOCH) * SEC_PER_DAY
+}
+
+#[derive(Clone, Debug)]
+pub struct TimeArchitecture {
+    pub leap_seconds: LeapSeconds,
+    pub corr_gain_offset: f64, // Clock offset correction gain per fix [0, 1]
+    pub corr_gain_drift: f64,  // Clock drift correction gain per fix
+    pub corr_resync_tol: f64,  // Offset residual forcing a hard resync [s]
+    pub corr_min_ticks: f64,   // Resync tolerance floor [GPSR clock ticks]
+    pub dt_tol: f64,           // Allowed cycle time error as fraction of nominal
+    pub dt_max: f64,           // Longest gap integrated through; beyond is a clock jump [s]
+}
+impl Default for TimeArchitecture {
+    fn default() -> Self {
+        Self {
+            leap_seconds: LeapSeconds::default(),
+            corr_gain_offset: 0.1,
+            corr_gain_drift: 0.01,
+            corr_resync_tol: 1e-3,
+            corr_min_ticks: 3.,
+            dt_tol: 0.2,
+            dt_max: 1.,
+        }
+    }
+}
+impl Param for TimeArchitecture {}
+
+// FSW clock + correlation to GPS time
+// gps = fsw + offset + drift * (fsw - t_ref)
+#[derive(Clone, Debug, Default)]
+pub struct TimeBus {
+    fsw_time: f64,    // FSW clock at cycle start [s]
+    cycle: u64,       // GNC cycles run
+    offset: f64,      // GPS - FSW at t_ref [s]
+    drift: f64,       // FSW clock rate error [s/s]
+    t_ref: f64,       // FSW time of last correlation [s]
+    correlated: bool, // Disciplined to GPSR at least once
+    dt: f64,          // Time since previous run; cycle, or rate group via with_dt [s]
+    cycle_dt: f64,    // Validated cycle time since previous cycle [s]
+    skipped: u32,     // Nominal cycles missed before this one
+    clock_jump: bool, // FSW clock discontinuity this cycle
+    jump_offset: f64, // Accumulated FSW clock jumps; sensor clocks don't see them [s]
+    started: bool,    // First cycle run
+}
+
+impl TimeBus {
+    // Latch cycle time and validate dt against the nominal rate
+    pub fn advance(&mut self, fsw_time: f64, prev_time: &TimeBus, param_bus: &ParamBus) {
+        self.clone_from(prev_time);
+        let arch = &param_bus.time;
+        let nominal = param_bus.gnc_dt;
+        self.cycle += 1;
+        self.skipped = 0;
+        self.clock_jump = false;
+
+        // Unusable clock; step the previous time one nominal cycle
+        let fsw_time = if fsw_time.is_finite() {
+            fsw_time
+        } else {
+            log::error!("Invalid FSW clock {}; assuming nominal dt", fsw_time);
+            prev_time.fsw_time + nominal
+        };
+
+        // Nothing to difference against on the first cycle
+        if !prev_time.started {
+            self.fsw_time = fsw_time;
+            self.dt = nominal;
+            self.cycle_dt = nominal;
+            self.started = true;
+            return;
+        }
+
+        let dt = fsw_time - prev_time.fsw_time;
+        if !(dt > 0. && dt <= arch.dt_max) {
+            // Clock jump; integrate one nominal step and carry GPS time across the discontinuity
+            log::error!(
+                "FSW clock jump {:.6} s -> {:.6} s; assuming nominal dt",
+                prev_time.fsw_time,
+                fsw_time
+            );
+            let jump = dt - nominal;
+            self.offset -= jump;
+            self.t_ref += jump;
+            self.jump_offset += jump;
+            self.dt = nominal;
+            self.clock_jump = true;
+        } else {
+            if (dt - nominal).abs() > arch.dt_tol * nominal {
+                self.skipped = ((dt / nominal).round() as u32).saturating_sub(1);
+                log::warn!(
+                    "Off-nominal GNC dt {:.6} s ({} cycles skipped)",
+                    dt,
+                    self.skipped
+                );
+            }
+            self.dt = dt;
+        }
+        self.cycle_dt = self.dt;
+        self.fsw_time = fsw_time;
+    }
+
+    // Discipline to the GPSR fix time
+    pub fn correlate(&mut self, tlm_sensor: &SensorBus, param_bus: &ParamBus) {
+        let Some((col, gps_fix)) = tlm_sensor
+            .gpsr_bus()
+            .filter(|gpsr| {
+                gpsr.fresh(self) && gpsr.hardware_subtest() == 0 && gpsr.fix_consistent()
+            })
+            .and_then(|gpsr| gpsr.selected().map(|col| (col, gpsr.fix_time(col))))
+        else {
+            return;
+        };
+
+        // FSW time of validity of the fix
+        let age = tlm_sensor.gpsr_bus().map_or(0., |gpsr| gpsr.age()[col]);
+        let t_fix = self.fsw_time - age;
+        self.discipline(t_fix, gps_fix.to_seconds(), param_bus);
+    }
+
+    // Discipline step; GPS seconds at FSW time t_fix
+    fn discipline(&mut self, t_fix: f64, gps_fix: f64, param_bus: &ParamBus) {
+        let arch = &param_bus.time;
+        let measured = gps_fix - t_fix;
+
+        let predicted = self.offset + self.drift * (t_fix - self.t_ref);
+        let residual = measured - predicted;
+
+        // Fix time is quantized to the GPSR clock tick; resync only beyond that noise
+        let resync_tol = arch
+            .corr_resync_tol
+            .max(arch.corr_min_ticks * param_bus.acs_sensors.gpsr_clock.tick);
+        if !self.correlated || residual.abs() > resync_tol {
+            if self.correlated {
+                log::warn!("FSW clock resync to GPS; residual {:.6} s", residual);
+            }
+            self.offset = measured;
+            self.drift = 0.;
+        } else {
+            let dt = t_fix - self.t_ref;
+            self.offset = predicted + arch.corr_gain_offset * residual;
+            if dt > 0. {
+                self.drift += arch.corr_gain_drift * residual / dt;
+            }
+        }
+        self.t_ref = t_fix;
+        self.correlated = true;
+    }
+
+    // Same cycle, dt over a rate group period; cycle_dt unchanged
+    pub fn with_dt(&self, dt: f64) -> Self {
+        Self { dt, ..self.clone() }
+    }
+
+    pub fn register(scheduler: &mut Scheduler) {
+        // Needs a fresh GPSR fix; runs after GPSR processing
+        scheduler.register(
+            "CLOCK",
+            RateGroup::NAV,
+            Phase::TIME,
+            |curr, _, _, param_bus| curr.time_bus.correlate(&curr.tlm_sensor_bus, param_bus),
+        );
+    }
+
+    pub fn fsw_time(&self) -> f64 {
+        self.fsw_time
+    }
+
+    pub fn cycle(&self) -> u64 {
+        self.cycle
+    }
+
+    pub fn correlated(&self) -> bool {
+        self.correlated
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    pub fn cycle_dt(&self) -> f64 {
+        self.cycle_dt
+    }
+
+    pub fn skipped(&self) -> u32 {
+        self.skipped
+    }
+
+    pub fn clock_jump(&self) -> bool {
+        self.clock_jump
+    }
+
+    pub fn jump_offset(&self) -> f64 {
+        self.jump_offset
+    }
+
+    // GPS seconds at an FSW time
+    pub fn fsw2gps(&self, fsw_time: f64) -> f64 {
+        fsw_time + self.offset + self.drift * (fsw_time - self.t_ref)
+    }
+
+    pub fn gps(&self) -> GpsTime {
+        GpsTime::from_seconds(self.fsw2gps(self.fsw_time))
+    }
+
+    pub fn tai(&self) -> f64 {
+        gps2tai(self.fsw2gps(self.fsw_time))
+    }
+
+    pub fn tt(&self) -> f64 {
+        tai2tt(self.tai())
+    }
+
+    pub fn utc(&self, leap: &LeapSeconds) -> f64 {
+        tai2utc(self.tai(), leap)
+    }
+
+    pub fn jd_tt(&self) -> f64 {
+        jd(self.tt())
+    }
+
+    pub fn jd_utc(&self, leap: &LeapSeconds) -> f64 {
+        jd(self.utc(leap))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2017-01-01 leap second; TAI - UTC 36 -> 37 s
+    #[test]
+    fn leap_second_boundary() {
+        let leap = LeapSeconds::default();
+        let utc_leap = mjd2seconds(57754.);
+
+        assert_eq!(leap.tai_utc_at_utc(utc_leap - 1.), 36.);
+        assert_eq!(leap.tai_utc_at_utc(utc_leap), 37.);
+        assert_eq!(leap.tai_utc(utc_leap + 36.), 36.); // 23:59:60
+        assert_eq!(leap.tai_utc(utc_leap + 37.), 37.);
+        for utc in [utc_leap - 1., utc_leap, utc_leap + 1.] {
+            assert_eq!(tai2utc(utc2tai(utc, &leap), &leap), utc);
+        }
+
+        // Before the table
+        assert_eq!(leap.tai_utc_at_utc(mjd2seconds(44000.)), TAI_UTC_PRE);
+    }
+
+    // GPS week 1930 starts 2017-01-01 00:00:00 GPS; GPS - UTC = 18 s
+    #[test]
+    fn gps_utc_round_trip() {
+        let leap = LeapSeconds::default();
+        let gps = GpsTime::new(1930, 3600.);
+        let utc = tai2utc(gps2tai(gps.to_seconds()), &leap);
+        assert_eq!(utc, mjd2seconds(57754.) + 3600. - 18.);
+        assert_eq!(GpsTime::from_seconds(tai2gps(utc2tai(utc, &leap))), gps);
+
+        // Week rollover
+        let gps = GpsTime::from_seconds(1930. * SEC_PER_WEEK - 0.5);
+        assert_eq!(gps, GpsTime::new(1929, SEC_PER_WEEK - 0.5));
+    }
+
+    // First fix syncs; small residuals are filtered, beyond a few GPSR ticks resyncs
+    #[test]
+    fn clock_discipline() {
+        let param_bus = ParamBus::default();
+        let arch = &param_bus.time;
+        let gps0 = 1e9;
+        let mut time_bus = TimeBus::default();
+
+        time_bus.discipline(100., gps0, &param_bus);
+        assert!(time_bus.correlated());
+        assert_eq!(time_bus.fsw2gps(100.), gps0);
+
+        // Within tick noise; offset moves by gain * residual, drift by gain * residual / dt
+        let residual = 0.2;
+        time_bus.discipline(110., gps0 + 10. + residual, &param_bus);
+        let gps_pred = gps0 + 10. + arch.corr_gain_offset * residual;
+        assert!((time_bus.fsw2gps(110.) - gps_pred).abs() < 1e-6);
+        assert!((time_bus.drift - arch.corr_gain_drift * residual / 10.).abs() < 1e-9);
+
+        // Beyond the tick floor
+        time_bus.discipline(120., gps0 + 25., &param_bus);
+        assert_eq!(time_bus.fsw2gps(120.), gps0 + 25.);
+        assert_eq!(time_bus.drift, 0.);
+    }
+
+    // Clock jump carries GPS time across; non-finite time steps nominal
+    #[test]
+    fn clock_jump() {
+        let param_bus = ParamBus::default();
+        let mut prev = TimeBus::default();
+        prev.advance(100., &TimeBus::default(), &param_bus);
+        prev.discipline(100., 1e9, &param_bus);
+
+        let mut curr = TimeBus::default();
+        curr.advance(5000., &prev, &param_bus);
+        assert!(curr.clock_jump());
+        assert_eq!(curr.dt(), param_bus.gnc_dt);
+        assert!((curr.fsw2gps(curr.fsw_time()) - (1e9 + param_bus.gnc_dt)).abs() < 1e-6);
+
+        let mut next = TimeBus::default();
+        next.advance(f64::NAN, &curr, &param_bus);
+        assert!(!next.clock_jump());
+        assert_eq!(next.fsw_time(), curr.fsw_time() + param_bus.gnc_dt);
+    }
+}